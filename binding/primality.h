@@ -13,6 +13,10 @@ extern "C" {
  bool is_prime(uint64_t x);
 
  bool is_prime_wc(uint64_t x);
+
+ bool is_prime_32(uint32_t x);
+
+ bool is_prime_wc_32(uint32_t x);
  
 /* 
  bool is_prime_128(__int128 x);
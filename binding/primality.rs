@@ -6,6 +6,10 @@ extern {
    fn is_prime(x: u64) -> bool;
    
    fn is_prime_wc(x: u64) -> bool;
+
+   fn is_prime_32(x: u32) -> bool;
+
+   fn is_prime_wc_32(x: u32) -> bool;
    /* 
    fn is_prime_128(x: u128) -> bool;
    
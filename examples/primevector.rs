@@ -1,4 +1,4 @@
-use machine_prime::is_prime;
+use machine_prime::{is_prime,Primes,ResiduePrimes};
 
 /*
    Some reference implementations
@@ -6,9 +6,10 @@ use machine_prime::is_prime;
    
    PrimeVector is novel implementation of attempting to speed up primality checking of
    
-   PrimeIter is a trivial iterator over the set of 64-bit primes
+   Primes is the library iterator over the set of 64-bit primes, see machine_prime::Primes
    
-   ResiduePrime is a realistic number-theoretic application that shows the real purpose of machine-prime;
+   ResiduePrimes is the library iterator over primes in an arithmetic progression, see machine_prime::ResiduePrimes.
+   It is a realistic number-theoretic application that shows the real purpose of machine-prime;
    checking primality of numbers of some specific form  across the entire interval (0;2^64], 
    without costly initialisation of all primes
 
@@ -52,105 +53,6 @@ impl PrimeVector{
      }
 }
 
-// Prime iterator, normally this would be constructed with an Erastothenes sieve
-// While Machine-prime is generally slower than sieves, it still generates about 300k primes per second
-#[derive(Copy,Clone)]
-struct PrimeIter{
-  p: u64
-}
-
-impl PrimeIter{
-  fn new(p: u64) -> Self{
-     PrimeIter{p}
-  }
-  // Sugar to make it look like a normal iterator
-  fn iter(&self) -> Self{
-     self.clone()
-  }
-}
-
-impl Iterator for PrimeIter {
-    type Item = u64;
-
-    fn next(&mut self) -> Option<Self::Item> {
-
-      loop{
-        if self.p > u64::MAX-57{
-           return None
-        }
-        self.p += 1;
-          if is_prime(self.p){
-            return Some(self.p)
-          }
-        }
-    }
-    
-   fn size_hint(&self) -> (usize,Option<usize>){
-       (2usize,Some((u64::MAX-58) as usize))
-   } 
-    
-   fn last(self) -> Option<Self::Item>{
-      Some(u64::MAX-58)
-   }
-}
-
-impl DoubleEndedIterator for PrimeIter{
-      
-    fn next_back(&mut self) -> Option<Self::Item>{
-
-      loop{
-        if self.p < 1{
-           return None
-        }
-        self.p -= 1;
-          if is_prime(self.p){
-            return Some(self.p)
-          }
-        }
-    }
-}
-
-
-// Primes of  the form x*Ring + A  i.e  x \equiv A (mod Ring)
-#[derive(Copy,Clone)]
-struct ResiduePrime<const A: u64,const RING: u64 >{
-  p: u64
-}
-
-
-impl<const A: u64, const RING: u64> ResiduePrime<A,RING>{
-
-
-  fn new(p: u64) -> Option<Self>{
-   // Checks that starting value is already a valid residue,
-   // but not necessarily prime
-    if p%RING == A{
-      return Some(Self{p})
-    }
-    None
-  }
-  
-}
-
-impl <const A: u64, const RING: u64> Iterator for ResiduePrime<A,RING>{
-     type Item = u64;
-     
-   fn next(&mut self) -> Option<Self::Item>{
-     
-      loop{
-        if self.p > u64::MAX-57{
-           return None
-        }
-        self.p += RING;
-        let x : u64 = self.p;
-          if is_prime(x){
-            return Some(x)
-          }
-        }
-   }  
-}
-
-
 fn main(){
   let mut prime_checker = PrimeVector::new();
   const BOUND: u64 = 6_000_000;
@@ -190,10 +92,9 @@ fn main(){
    
   let mut count = 0u64;
    
-  let P = PrimeIter::new(2);
     let start = std::time::Instant::now();
    
-  for primes in P.iter(){
+  for _ in Primes::new(2,u64::MAX){
    count+=1;
     if count > 1_000_000{
        break;
@@ -204,7 +105,7 @@ fn main(){
   println!("Sequentially generated 1 million primes in t: {:?}",stop);
   
   // Some candidate factors for base 15 fermat pseudoprimes
-  let candidate_15 = ResiduePrime::<1,29130>::new(1).unwrap();
+  let candidate_15 = ResiduePrimes::new(1,29130,2,u64::MAX).unwrap();
    
       let start = std::time::Instant::now();
       let mut count = 0u64;
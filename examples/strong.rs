@@ -1,4 +1,4 @@
-use machine_prime::{strong_fermat_128,one_mont_128,two_mont_128,to_mont_128,lucas_128,mul_inv2_128};
+use machine_prime::{PrimalityContext128,almost_extra_strong_lucas_128};
 
 /*
 
@@ -14,50 +14,41 @@ use machine_prime::{strong_fermat_128,one_mont_128,two_mont_128,to_mont_128,luca
   
   
   
-  Note that running this example requires using the "internal" feature
+  Note that running this example requires using the "wide" feature
   
   A practical implementation will employ trial division like the library function but this is omitted 
   for simplicity.  A slighly simpler implementation would be to remove the base 2 and lucas test and run the test
-  after the library is_prime_128 function. The initialisation values would have to be computed twice, however. 
+  after the library is_prime_128 function. The initialisation values would have to be computed twice, however.
+  
+  PrimalityContext128 computes the Montgomery form of 1, 2 and N-1, the inverse of N and the trailing zeros of N-1
+  once, and reuses them for every base.
   
   Approximate run time 21.5 Fermat tests
 */
 
 fn strong_test(x: u128) -> bool{
-   // We cannot use this algorithm for n < 2^64 due to a Montgomery transform "error" 
-   // when using 64-bit inputs for a 128-bit transform.
+   // We cannot use this algorithm for n < 2^64, the witnesses were chosen for larger n
     debug_assert!(x > 1u128<<64);
-    // X must be odd to ensure that the inverse over 2^128 can be calculated
-    debug_assert!(x&1==1);
     
-   // Initialise necessary values
-   
-    let inv = mul_inv2_128(x);
-
-    let tzc = x.wrapping_sub(1).trailing_zeros();
-    let one = one_mont_128(x);
-    let oneinv = x.wrapping_sub(one);
-    let two = two_mont_128(one, x);
+   // Initialise necessary values, None if x is even
+    let ctx = match PrimalityContext128::new(x) {
+        Some(ctx) => ctx,
+        None => return false,
+    };
 
    // Witness using 2. Nearly all composites will be eliminated by this
    // You can remove this if it is being used as a complement to the is_prime_128 library function
-    if !strong_fermat_128(x, tzc, two, one, oneinv, inv) {
+    if !ctx.sprp(2) {
         return false;
     }
 
    // Fermat component alone should be sufficient
    // The witnesses were computed from pseudoprimes to the prime witnesses 2;47
-   for i in [3,5,7,11,13,17,19,23,29,31,37,41,43,47,511,659,679,8129,70157]{
-       let montbase = to_mont_128(i,x);
-       
-       if !strong_fermat_128(x,tzc,montbase,one,oneinv,inv){
-          return false;
-       }
-   }
+    if !ctx.sprp_bases(&[3,5,7,11,13,17,19,23,29,31,37,41,43,47,511,659,679,8129,70157]) {
+        return false;
+    }
    
-   // Add a Lucas sequence test
-   // Also removable if used as a complement
-    lucas_128(x,one,two,inv)
+   // Add a Lucas-V sequence test, Q = 1 and the first P in 3,4,5,... such that (P^2-4/x) = -1
+   // This is the Lucas test is_prime_128 uses with the "wide" feature. Also removable if used as a complement
+    almost_extra_strong_lucas_128(x,1)
 }
-
-
@@ -0,0 +1,440 @@
+#[cfg(feature = "ssmr")]
+use crate::hashbase::FERMAT_WITNESS;
+
+#[cfg(feature = "ssmr")]
+use crate::check::witness_selector;
+
+#[cfg(all(not(feature = "ssmr"), any(feature = "lucas", not(feature = "qft"))))]
+use crate::check::lucas;
+
+#[cfg(all(feature = "qft", not(any(feature = "lucas", feature = "ssmr"))))]
+use crate::qft_64::qft_64;
+
+#[cfg(any(feature = "lucas", feature = "ssmr"))]
+use crate::primes::PRIME_TABLE;
+
+#[cfg(not(feature = "simd"))]
+use crate::check::{mont_prod, to_mont};
+
+#[cfg(any(not(feature = "simd"), not(feature = "ssmr")))]
+use crate::check::{mul_inv2, one_mont, two_mont};
+use crate::narrow::is_prime_32;
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wide::{mont_prod_128, mont_sqr_128, mul_inv2_128, one_mont_128, two_mont_128};
+
+#[cfg(all(any(feature = "lucas", feature = "ssmr"), any(feature = "wide", feature = "qft")))]
+use crate::primes::PRIME_TABLE_128;
+
+#[cfg(all(feature = "wide", not(feature = "qft")))]
+use crate::wide::lucas_128;
+
+#[cfg(feature = "qft")]
+use crate::qft::qft;
+
+#[cfg(feature = "simd")]
+use core::simd::{Mask, Simd};
+
+#[cfg(feature = "simd")]
+use crate::simd::strong_fermat_simd;
+
+#[cfg(all(feature = "simd", feature = "ssmr"))]
+use crate::simd::witness_selector_simd;
+
+/*
+   Batch primality
+
+   A single strong fermat test is one long chain of dependent Montgomery products, each one waiting on the
+   latency of the previous multiplication. Evaluating several candidates at once in lockstep fills those gaps,
+   as the ladders of different candidates are independent and can be issued back to back.
+
+   Candidates are first filtered by trial division, the survivors are collected into groups of LANES and
+   their base-2 ladders are interleaved. Survivors of the base-2 test (almost always primes) then have their
+   second test interleaved in the same way. Results are identical to is_prime and is_prime_128.
+
+   With the SIMD feature the 64-bit lanes are evaluated by strong_fermat_simd instead.
+*/
+
+/// Number of candidates evaluated in lockstep
+#[cfg(not(feature = "simd"))]
+const LANES: usize = 4;
+
+/// Number of candidates evaluated in lockstep, a single vector with the SIMD feature
+#[cfg(feature = "simd")]
+const LANES: usize = 8;
+
+#[cfg(feature = "ssmr")]
+#[inline(always)]
+fn prefetch<T>(ptr: *const T) {
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        core::arch::x86_64::_mm_prefetch(ptr as *const i8, core::arch::x86_64::_MM_HINT_T0);
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        let _ = ptr;
+    }
+}
+
+// In: X
+// Out: Some(primality) if X is decided without a strong fermat test, None otherwise
+const fn filter(x: u64) -> Option<bool> {
+    if x < 0x100000000 {
+        return Some(is_prime_32(x as u32));
+    }
+
+    if x & 1 == 0 {
+        return Some(false);
+    }
+
+    #[cfg(any(feature = "lucas", feature = "ssmr"))]
+    {
+        let mut idx: usize = 0;
+
+        while idx < 256 {
+            let prod = x.wrapping_mul(PRIME_TABLE[idx]);
+
+            if prod <= PRIME_TABLE[idx + 1] {
+                return Some(prod == 1);
+            }
+            idx += 2;
+        }
+    }
+    None
+}
+
+// In: N,base for each lane, lanes past len are ignored
+// Out: SPRP(N,base) for each lane
+#[cfg(feature = "simd")]
+fn strong_fermat_lanes(n: &[u64; LANES], base: &[u64; LANES], len: usize) -> [bool; LANES] {
+    let active = Mask::from_array(core::array::from_fn(|i| i < len));
+    let mut n = *n;
+    // Inactive lanes only need an odd modulus
+    for x in n.iter_mut().skip(len) {
+        *x = 3;
+    }
+    strong_fermat_simd(Simd::from_array(n), Simd::from_array(*base), active).to_array()
+}
+
+// In: N,base for each lane, lanes past len are ignored
+// Out: SPRP(N,base) for each lane
+#[cfg(not(feature = "simd"))]
+fn strong_fermat_lanes(n: &[u64; LANES], base: &[u64; LANES], len: usize) -> [bool; LANES] {
+    if len == 0 {
+        return [false; LANES];
+    }
+    // Unused lanes repeat the first so that the ladder always runs over every lane
+    let mut n = *n;
+    let mut base = *base;
+    for i in len..LANES {
+        n[i] = n[0];
+        base[i] = base[0];
+    }
+
+    let mut inv = [0u64; LANES];
+    let mut one = [0u64; LANES];
+    let mut oneinv = [0u64; LANES];
+    let mut tz = [0u32; LANES];
+    let mut d = [0u64; LANES];
+    let mut b = [0u64; LANES];
+
+    let mut bits = 0;
+    let mut maxtz = 0;
+
+    for i in 0..LANES {
+        inv[i] = mul_inv2(n[i]);
+        one[i] = one_mont(n[i]);
+        oneinv[i] = n[i].wrapping_sub(one[i]);
+        tz[i] = (n[i] - 1).trailing_zeros();
+        d[i] = n[i] >> tz[i];
+        b[i] = if base[i] == 2 {
+            two_mont(one[i], n[i])
+        } else {
+            to_mont(base[i], n[i])
+        };
+        bits = bits.max(64 - d[i].leading_zeros());
+        maxtz = maxtz.max(tz[i]);
+    }
+
+    // Right-to-left ladder so that every lane follows the same sequence of operations, the product is
+    // selected rather than branched on as the bits of d are unpredictable
+    let mut result = one;
+
+    for _ in 0..bits {
+        for i in 0..LANES {
+            let prod = mont_prod(result[i], b[i], inv[i], n[i]);
+            result[i] = if d[i] & 1 == 1 { prod } else { result[i] };
+            b[i] = mont_prod(b[i], b[i], inv[i], n[i]);
+            d[i] >>= 1;
+        }
+    }
+
+    let mut pass = [false; LANES];
+
+    for i in 0..len {
+        pass[i] = result[i] == one[i] || result[i] == oneinv[i];
+    }
+
+    for count in 1..maxtz {
+        for i in 0..len {
+            if !pass[i] && count < tz[i] {
+                result[i] = mont_prod(result[i], result[i], inv[i], n[i]);
+                pass[i] = result[i] == oneinv[i];
+            }
+        }
+    }
+    pass
+}
+
+// In: Candidates that survived trial division, their positions in out
+// Out: Writes the primality of each candidate
+fn evaluate_lanes(n: &[u64; LANES], pos: &[usize; LANES], len: usize, out: &mut [bool]) {
+    #[allow(unused_mut)]
+    let mut base = [2u64; LANES];
+    // Lanes that are decided by the first test
+    #[allow(unused_mut)]
+    let mut single = [false; LANES];
+
+    // As in is_prime, n < 2^47 is decided by its witness alone. The remaining witnesses are only needed
+    // after the base-2 ladders, so start loading them now
+    #[cfg(feature = "ssmr")]
+    for i in 0..len {
+        if n[i] < 0x800000000000 {
+            base[i] = witness_selector(n[i]);
+            single[i] = true;
+        } else {
+            prefetch(&FERMAT_WITNESS[((n[i] as u32).wrapping_mul(811484239) >> 14) as usize]);
+        }
+    }
+
+    let first = strong_fermat_lanes(n, &base, len);
+
+    let mut survivor = [0u64; LANES];
+    let mut survivor_pos = [0usize; LANES];
+    let mut k = 0;
+
+    for i in 0..len {
+        if first[i] && !single[i] {
+            survivor[k] = n[i];
+            survivor_pos[k] = pos[i];
+            k += 1;
+        } else {
+            out[pos[i]] = first[i];
+        }
+    }
+
+    #[cfg(feature = "ssmr")]
+    {
+        #[cfg(feature = "simd")]
+        let wit = witness_selector_simd(Simd::from_array(survivor)).to_array();
+        #[cfg(not(feature = "simd"))]
+        let mut wit = [0u64; LANES];
+        #[cfg(not(feature = "simd"))]
+        for i in 0..k {
+            wit[i] = witness_selector(survivor[i]);
+        }
+        let second = strong_fermat_lanes(&survivor, &wit, k);
+        for i in 0..k {
+            out[survivor_pos[i]] = second[i];
+        }
+    }
+
+    #[cfg(not(feature = "ssmr"))]
+    for i in 0..k {
+        let x = survivor[i];
+        let sqrt = x.isqrt();
+        out[survivor_pos[i]] = if sqrt * sqrt == x {
+            false
+        } else {
+            let one = one_mont(x);
+            #[cfg(all(feature = "qft", not(feature = "lucas")))]
+            let second = qft_64(x, one, two_mont(one, x), x - one, mul_inv2(x));
+            #[cfg(any(feature = "lucas", not(feature = "qft")))]
+            let second = lucas(x, one, two_mont(one, x), mul_inv2(x));
+            second
+        };
+    }
+}
+
+/// Primality of a batch of 64-bit integers
+///
+/// In: X, out
+///
+/// Out: out\[i\] = is_prime(X\[i\]) for the first min(X.len(), out.len()) elements
+pub fn is_prime_batch(x: &[u64], out: &mut [bool]) {
+    let len = x.len().min(out.len());
+
+    let mut lane = [0u64; LANES];
+    let mut pos = [0usize; LANES];
+    let mut k = 0;
+
+    for i in 0..len {
+        match filter(x[i]) {
+            Some(flag) => out[i] = flag,
+            None => {
+                lane[k] = x[i];
+                pos[k] = i;
+                k += 1;
+                if k == LANES {
+                    evaluate_lanes(&lane, &pos, k, out);
+                    k = 0;
+                }
+            }
+        }
+    }
+    if k != 0 {
+        evaluate_lanes(&lane, &pos, k, out);
+    }
+}
+
+// In: X > 2^64
+// Out: Some(primality) if X is decided without a strong fermat test, None otherwise
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn filter_128(x: u128) -> Option<bool> {
+    if x & 1 == 0 {
+        return Some(false);
+    }
+
+    #[cfg(any(feature = "lucas", feature = "ssmr"))]
+    {
+        let mut idx: usize = 0;
+
+        while idx < 256 {
+            let prod = x.wrapping_mul(PRIME_TABLE_128[idx]);
+
+            if prod <= PRIME_TABLE_128[idx + 1] {
+                return Some(prod == 1);
+            }
+            idx += 2;
+        }
+    }
+    None
+}
+
+// In: N for each lane, lanes past len are ignored
+// Out: SPRP(N,2) for each lane
+#[cfg(any(feature = "wide", feature = "qft"))]
+fn strong_fermat_lanes_128(n: &[u128; LANES], len: usize) -> [bool; LANES] {
+    if len == 0 {
+        return [false; LANES];
+    }
+    let mut n = *n;
+    for i in len..LANES {
+        n[i] = n[0];
+    }
+
+    let mut inv = [0u128; LANES];
+    let mut one = [0u128; LANES];
+    let mut oneinv = [0u128; LANES];
+    let mut tz = [0u32; LANES];
+    let mut d = [0u128; LANES];
+    let mut b = [0u128; LANES];
+
+    let mut bits = 0;
+    let mut maxtz = 0;
+
+    for i in 0..LANES {
+        inv[i] = mul_inv2_128(n[i]);
+        one[i] = one_mont_128(n[i]);
+        oneinv[i] = n[i].wrapping_sub(one[i]);
+        tz[i] = (n[i] - 1).trailing_zeros();
+        d[i] = n[i] >> tz[i];
+        b[i] = two_mont_128(one[i], n[i]);
+        bits = bits.max(128 - d[i].leading_zeros());
+        maxtz = maxtz.max(tz[i]);
+    }
+
+    let mut result = one;
+
+    for _ in 0..bits {
+        for i in 0..LANES {
+            let prod = mont_prod_128(result[i], b[i], inv[i], n[i]);
+            result[i] = if d[i] & 1 == 1 { prod } else { result[i] };
+            b[i] = mont_sqr_128(b[i], inv[i], n[i]);
+            d[i] >>= 1;
+        }
+    }
+
+    let mut pass = [false; LANES];
+
+    for i in 0..len {
+        pass[i] = result[i] == one[i] || result[i] == oneinv[i];
+    }
+
+    for count in 1..maxtz {
+        for i in 0..len {
+            if !pass[i] && count < tz[i] {
+                result[i] = mont_sqr_128(result[i], inv[i], n[i]);
+                pass[i] = result[i] == oneinv[i];
+            }
+        }
+    }
+    pass
+}
+
+// In: Candidates that survived trial division, their positions in out
+// Out: Writes the primality of each candidate
+#[cfg(any(feature = "wide", feature = "qft"))]
+fn evaluate_lanes_128(n: &[u128; LANES], pos: &[usize; LANES], len: usize, out: &mut [bool]) {
+    let first = strong_fermat_lanes_128(n, len);
+
+    for i in 0..len {
+        let x = n[i];
+        out[pos[i]] = if !first[i] {
+            false
+        } else {
+            let sqrt = x.isqrt();
+            if sqrt * sqrt == x {
+                false
+            } else {
+                let inv = mul_inv2_128(x);
+                let one = one_mont_128(x);
+                let two = two_mont_128(one, x);
+                #[cfg(feature = "qft")]
+                {
+                    qft(x, one, two, x.wrapping_sub(one), inv)
+                }
+                #[cfg(not(feature = "qft"))]
+                {
+                    lucas_128(x, one, two, inv)
+                }
+            }
+        };
+    }
+}
+
+/// Primality of a batch of 128-bit integers
+///
+/// In: X, out
+///
+/// Out: out\[i\] = is_prime_128(X\[i\]) for the first min(X.len(), out.len()) elements
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub fn is_prime_batch_128(x: &[u128], out: &mut [bool]) {
+    let len = x.len().min(out.len());
+
+    let mut lane = [0u128; LANES];
+    let mut pos = [0usize; LANES];
+    let mut k = 0;
+
+    for i in 0..len {
+        if x[i] < 0x10000000000000000 {
+            out[i] = crate::check::is_prime(x[i] as u64);
+            continue;
+        }
+        match filter_128(x[i]) {
+            Some(flag) => out[i] = flag,
+            None => {
+                lane[k] = x[i];
+                pos[k] = i;
+                k += 1;
+                if k == LANES {
+                    evaluate_lanes_128(&lane, &pos, k, out);
+                    k = 0;
+                }
+            }
+        }
+    }
+    if k != 0 {
+        evaluate_lanes_128(&lane, &pos, k, out);
+    }
+}
@@ -0,0 +1,356 @@
+use crate::check::{is_prime, mont_pow, mul_inv2, one_mont, to_mont};
+use crate::factor::factor;
+use crate::order::least_primitive_root;
+
+/*
+  Pratt certificates for 64-bit primes
+
+  A prime p is certified by a witness g such that
+
+      g^(p-1) = 1 mod p  and  g^((p-1)/q) != 1 mod p  for every prime q | p-1
+
+  which forces g to have order p-1 and therefore p to be prime (Lucas' theorem). Each odd prime q dividing p-1
+  must in turn be certified, the recursion terminates at 2.
+
+  Rather than storing the recursion as a tree, the certificate is a flat list of (prime, witness) pairs. A verifier only
+  needs to check that p-1 is completely factored by 2 and the smaller primes in the list, and that the witness has full order.
+  By induction on the size of the primes every entry is then prime, including n itself.
+
+  The number of odd primes in the tree of p is bounded by log2(p) as the product of the odd primes dividing p-1 is at
+  most (p-1)/2. So 64 entries are always sufficient for n < 2^64.
+*/
+
+/// Maximum number of entries in a certificate
+pub const CERT_CAPACITY: usize = 64;
+
+/// Pratt certificate of primality for 64-bit integers
+///
+/// The first entry is always N itself, N = 2 has no entries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Certificate {
+    n: u64,
+    len: usize,
+    prime: [u64; CERT_CAPACITY],
+    witness: [u64; CERT_CAPACITY],
+}
+
+impl Certificate {
+    const fn empty(n: u64) -> Self {
+        Self {
+            n,
+            len: 0,
+            prime: [0; CERT_CAPACITY],
+            witness: [0; CERT_CAPACITY],
+        }
+    }
+
+    /// The integer certified prime
+    pub const fn n(&self) -> u64 {
+        self.n
+    }
+
+    /// Number of (prime, witness) pairs
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the certificate has no entries, which only holds for N = 2
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// (prime, witness) pair at idx
+    pub const fn get(&self, idx: usize) -> Option<(u64, u64)> {
+        if idx < self.len {
+            return Some((self.prime[idx], self.witness[idx]));
+        }
+        None
+    }
+
+    /// Construct a certificate from its pairs, the first of which must be N
+    ///
+    /// Returns None if there are more than CERT_CAPACITY pairs. The result is not checked, call verify_certificate.
+    pub const fn from_pairs(n: u64, pairs: &[(u64, u64)]) -> Option<Self> {
+        if pairs.len() > CERT_CAPACITY {
+            return None;
+        }
+        let mut cert = Self::empty(n);
+        while cert.len < pairs.len() {
+            cert.prime[cert.len] = pairs[cert.len].0;
+            cert.witness[cert.len] = pairs[cert.len].1;
+            cert.len += 1;
+        }
+        Some(cert)
+    }
+
+    const fn contains(&self, p: u64) -> bool {
+        let mut idx = 0;
+        while idx < self.len {
+            if self.prime[idx] == p {
+                return true;
+            }
+            idx += 1;
+        }
+        false
+    }
+
+    /// Binary encoding
+    ///
+    /// N, the number of pairs, and then each prime and witness as LEB128 varints
+    ///
+    /// Returns the number of bytes written or None if buf is too small. 11 + 20*len bytes is always sufficient.
+    pub const fn to_bytes(&self, buf: &mut [u8]) -> Option<usize> {
+        let mut pos = match write_varint(self.n, buf, 0) {
+            Some(x) => x,
+            None => return None,
+        };
+        pos = match write_varint(self.len as u64, buf, pos) {
+            Some(x) => x,
+            None => return None,
+        };
+        let mut idx = 0;
+        while idx < self.len {
+            pos = match write_varint(self.prime[idx], buf, pos) {
+                Some(x) => x,
+                None => return None,
+            };
+            pos = match write_varint(self.witness[idx], buf, pos) {
+                Some(x) => x,
+                None => return None,
+            };
+            idx += 1;
+        }
+        Some(pos)
+    }
+
+    /// Decode the binary encoding produced by to_bytes
+    pub const fn from_bytes(buf: &[u8]) -> Option<Self> {
+        let (n, mut pos) = match read_varint(buf, 0) {
+            Some(x) => x,
+            None => return None,
+        };
+        let len = match read_varint(buf, pos) {
+            Some((len, next)) => {
+                pos = next;
+                len
+            }
+            None => return None,
+        };
+        if len > CERT_CAPACITY as u64 {
+            return None;
+        }
+        let mut cert = Self::empty(n);
+        while cert.len < len as usize {
+            match read_varint(buf, pos) {
+                Some((p, next)) => {
+                    cert.prime[cert.len] = p;
+                    pos = next;
+                }
+                None => return None,
+            }
+            match read_varint(buf, pos) {
+                Some((g, next)) => {
+                    cert.witness[cert.len] = g;
+                    pos = next;
+                }
+                None => return None,
+            }
+            cert.len += 1;
+        }
+        if pos != buf.len() {
+            return None;
+        }
+        Some(cert)
+    }
+}
+
+/// Text encoding, N followed by each pair e.g "23;23:5,11:2,5:2"
+impl core::fmt::Display for Certificate {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.n)?;
+        let mut idx = 0;
+        while idx < self.len {
+            let sep = if idx == 0 { ';' } else { ',' };
+            write!(f, "{}{}:{}", sep, self.prime[idx], self.witness[idx])?;
+            idx += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Error parsing the text encoding of a certificate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseCertificateError;
+
+impl core::fmt::Display for ParseCertificateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid certificate encoding")
+    }
+}
+
+impl core::str::FromStr for Certificate {
+    type Err = ParseCertificateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (n, pairs) = match s.split_once(';') {
+            Some((n, pairs)) => (n, Some(pairs)),
+            None => (s, None),
+        };
+        let mut cert = Self::empty(n.trim().parse().map_err(|_| ParseCertificateError)?);
+
+        if let Some(pairs) = pairs {
+            for pair in pairs.split(',') {
+                let (p, g) = pair.split_once(':').ok_or(ParseCertificateError)?;
+                if cert.len == CERT_CAPACITY {
+                    return Err(ParseCertificateError);
+                }
+                cert.prime[cert.len] = p.trim().parse().map_err(|_| ParseCertificateError)?;
+                cert.witness[cert.len] = g.trim().parse().map_err(|_| ParseCertificateError)?;
+                cert.len += 1;
+            }
+        }
+        Ok(cert)
+    }
+}
+
+// In: X, buffer, position
+// Out: position after writing X as LEB128
+const fn write_varint(mut x: u64, buf: &mut [u8], mut pos: usize) -> Option<usize> {
+    loop {
+        if pos >= buf.len() {
+            return None;
+        }
+        if x < 0x80 {
+            buf[pos] = x as u8;
+            return Some(pos + 1);
+        }
+        buf[pos] = (x as u8) | 0x80;
+        x >>= 7;
+        pos += 1;
+    }
+}
+
+// In: buffer, position
+// Out: LEB128 value, position after it
+const fn read_varint(buf: &[u8], mut pos: usize) -> Option<(u64, usize)> {
+    let mut x = 0u64;
+    let mut shift = 0;
+    loop {
+        if pos >= buf.len() || shift > 63 {
+            return None;
+        }
+        let byte = buf[pos] as u64;
+        if shift == 63 && byte > 1 {
+            return None;
+        }
+        x |= (byte & 0x7F) << shift;
+        pos += 1;
+        if byte < 0x80 {
+            return Some((x, pos));
+        }
+        shift += 7;
+    }
+}
+
+/// Construct a Pratt certificate
+///
+/// In: N
+///
+/// Out: Certificate if N is prime, None otherwise
+pub const fn prime_certificate(n: u64) -> Option<Certificate> {
+    if !is_prime(n) {
+        return None;
+    }
+
+    let mut cert = Certificate::empty(n);
+
+    if n == 2 {
+        return Some(cert);
+    }
+
+    cert.prime[0] = n;
+    cert.len = 1;
+
+    // The certificate doubles as the work list, entries past idx have not had their witness computed
+    let mut idx = 0;
+
+    while idx < cert.len {
+        let p = cert.prime[idx];
+        let factors = factor(p - 1);
+
+        let mut j = 0;
+        while let Some((q, _)) = factors.get(j) {
+            if q != 2 && !cert.contains(q) {
+                cert.prime[cert.len] = q;
+                cert.len += 1;
+            }
+            j += 1;
+        }
+        cert.witness[idx] = least_primitive_root(p, &factors);
+        idx += 1;
+    }
+    Some(cert)
+}
+
+/// Verify a Pratt certificate
+///
+/// In: Certificate
+///
+/// Out: True if the certificate proves N prime
+pub const fn verify_certificate(cert: &Certificate) -> bool {
+    let n = cert.n;
+
+    if n == 2 {
+        return true;
+    }
+    if n < 3 || n & 1 == 0 || cert.len == 0 || cert.len > CERT_CAPACITY || !cert.contains(n) {
+        return false;
+    }
+
+    let mut idx = 0;
+
+    while idx < cert.len {
+        let p = cert.prime[idx];
+        let g = cert.witness[idx];
+
+        if p < 3 || p & 1 == 0 || g < 2 || g >= p {
+            return false;
+        }
+
+        let inv = mul_inv2(p);
+        let one = one_mont(p);
+        let base = to_mont(g, p);
+
+        // Fermat condition
+        if mont_pow(base, one, p - 1, inv, p) != one {
+            return false;
+        }
+
+        // Order condition for 2
+        let mut rem = (p - 1) >> (p - 1).trailing_zeros();
+        if mont_pow(base, one, (p - 1) >> 1, inv, p) == one {
+            return false;
+        }
+
+        // Order condition for the odd primes, only smaller entries may be used to factor p-1
+        let mut j = 0;
+        while j < cert.len {
+            let q = cert.prime[j];
+            if q < p && rem.is_multiple_of(q) {
+                while rem.is_multiple_of(q) {
+                    rem /= q;
+                }
+                if mont_pow(base, one, (p - 1) / q, inv, p) == one {
+                    return false;
+                }
+            }
+            j += 1;
+        }
+
+        if rem != 1 {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
@@ -7,6 +7,12 @@ use crate::primes::{INV_8,PRIME_TABLE};
 #[cfg(all(feature = "lucas", not( feature = "ssmr")))]
 use crate::primes::LUCAS_PARAM;
 
+#[cfg(all(not(feature = "ssmr"), any(feature = "lucas", not(feature = "qft"))))]
+use crate::symbol::jacobi;
+
+#[cfg(all(feature = "qft", not(any(feature = "lucas", feature = "ssmr"))))]
+use crate::qft_64::qft_64;
+
 //#[cfg(feature = "wide")]
 //use crate::double::{is_prime_128, is_prime_wc_128};
 
@@ -17,7 +23,9 @@ use crate::primes::LUCAS_PARAM;
 /// Out: n^-1
 pub const fn mul_inv2(n: u64) -> u64 {
     #[cfg(not(any(feature = "lucas", feature = "ssmr")))]
-    {        assert!(false);
+    {   // The QFT mode of is_prime runs without the INV_8 table
+        #[cfg(not(feature = "qft"))]
+        assert!(false);
         let mut est: u64 = 3u64.wrapping_mul(n) ^ 2;
         est = 2u64.wrapping_sub(est.wrapping_mul(n)).wrapping_mul(est);
         est = 2u64.wrapping_sub(est.wrapping_mul(n)).wrapping_mul(est);
@@ -48,6 +56,22 @@ pub const fn mul_inv2(n: u64) -> u64 {
     }
 }
 
+/// Multiplicative inverse over Z/2^64 without the INV_8 table
+///
+/// Newton iteration from the 5-bit estimate 3n^2, available in every build.
+///
+///  In:  n \in 2Z + 1
+///
+/// Out: n^-1
+pub(crate) const fn mul_inv2_newton(n: u64) -> u64 {
+    let mut est: u64 = 3u64.wrapping_mul(n) ^ 2;
+    est = 2u64.wrapping_sub(est.wrapping_mul(n)).wrapping_mul(est);
+    est = 2u64.wrapping_sub(est.wrapping_mul(n)).wrapping_mul(est);
+    est = 2u64.wrapping_sub(est.wrapping_mul(n)).wrapping_mul(est);
+    est = 2u64.wrapping_sub(est.wrapping_mul(n)).wrapping_mul(est);
+    est
+}
+
 const fn widening_mul(x: u64, y: u64) -> (u64,u64){
       let prod = x as u128 * y as u128;
       (prod as u64, (prod >> 64) as u64)
@@ -111,13 +135,42 @@ pub const fn mont_sub(x: u64, y: u64, n: u64) -> u64 {
     }
 }
 
+// In: X,Y < N
+// Out: X+Y mod N
+pub(crate) const fn add_mod(x: u64, y: u64, n: u64) -> u64 {
+    let (sum, carry) = x.overflowing_add(y);
+    if carry || sum >= n {
+        return sum.wrapping_sub(n);
+    }
+    sum
+}
+
+// In: X < N, N odd
+// Out: X/2 mod N
+pub(crate) const fn half_mod(x: u64, n: u64) -> u64 {
+    if x & 1 == 0 {
+        return x >> 1;
+    }
+    (x >> 1) + (n >> 1) + 1
+}
+
+// In: X, N > 0
+// Out: X mod N
+pub(crate) const fn signed_mod(x: i128, n: u64) -> u64 {
+    let rem = x.unsigned_abs() % n as u128;
+    if x < 0 && rem != 0 {
+        return n - rem as u64;
+    }
+    rem as u64
+}
+
 /*
    Possible optimizations
    
    Since this is only used for searching for Selfridge's parameter, a is always of the form (p-2)(p+2)
    can we only evaluate one component and therefore restrict arithmetic to 32-bit?
    
-   This algorithm is David Sparks' branchless variant of the original. 
+   The symbol is evaluated by jacobi, David Sparks' branchless variant of the original.
 */
 
 /// Check if non-quadratic residue
@@ -125,31 +178,17 @@ pub const fn mont_sub(x: u64, y: u64, n: u64) -> u64 {
 ///  In: A,N where N is odd
 ///
 /// Out: Jacobi(A,N) == -1
-#[cfg(not(feature = "ssmr"))]
-pub const fn nqr(mut a: u64, mut n: u64) -> bool {
- 
-     let mut sign : u32 = 0;
-     
-     while a != 0{
-        // Remove factors of a that are powers of two 
-        let twofactor = a.trailing_zeros();
-        a>>=twofactor;
-        // flip sign if p == 3 or 5 mod 8 and zeros is odd
-        sign ^= (n as u32).wrapping_add(2) >> 1 & twofactor << 1;
-        // if p == n == 3 mod 4 flip sign
-        sign ^= (n & a) as u32;
-        
-        (a,n) = (n%a,a); 
-     }
-     n == 1 && sign & 2 != 0
- }
+#[cfg(all(not(feature = "ssmr"), any(feature = "lucas", not(feature = "qft"))))]
+pub const fn nqr(a: u64, n: u64) -> bool {
+    jacobi(a, n) == -1
+}
 
 /// Lucas parameter search
 ///
 ///  In: N
 ///
 /// Out: x := jacobi(x*x-4,N) == -1
-#[cfg(not(feature = "ssmr"))]
+#[cfg(all(not(feature = "ssmr"), any(feature = "lucas", not(feature = "qft"))))]
 pub const fn param_search(n: u64) -> u64 {
     #[cfg(feature = "lucas")]
     {
@@ -206,7 +245,7 @@ pub const fn param_search(n: u64) -> u64 {
 /// In: N,Mont(1,N), Mont(2,N), N^-1
 ///
 /// Out: Lucas_V(n)
-#[cfg(not(feature = "ssmr"))]
+#[cfg(all(not(feature = "ssmr"), any(feature = "lucas", not(feature = "qft"))))]
 pub const fn lucas(n: u64, one: u64, two: u64, inv: u64) -> bool {
     let param = param_search(n);
 
@@ -377,7 +416,14 @@ const fn core_primality(x: u64) -> bool {
            return false;
         }
 
-        lucas(x, one, two, inv)
+        #[cfg(all(feature = "qft", not(feature = "lucas")))]
+        {   // Khashin's QFT in place of the Lucas test, neither needs a table
+            qft_64(x, one, two, oneinv, inv)
+        }
+        #[cfg(any(feature = "lucas", not(feature = "qft")))]
+        {
+            lucas(x, one, two, inv)
+        }
     }
 }
 
@@ -397,6 +443,10 @@ pub const extern "C" fn is_prime(x: u64) -> bool {
     if x & 1 == 0 {
         return false;
     }
+    // 32-bit arithmetic is considerably faster
+    if x < 0x100000000 {
+        return crate::narrow::is_prime_32(x as u32);
+    }
 
     #[cfg(any(feature = "lucas", feature = "ssmr"))]
     {
@@ -429,6 +479,8 @@ pub const extern "C" fn is_prime(x: u64) -> bool {
 /// No additional errors
 /// # Tiny
 /// No additional errors
+/// # QFT without SSMR or Lucas
+/// No additional errors
 #[no_mangle]
 pub const extern "C" fn is_prime_wc(x: u64) -> bool {
     /*
@@ -441,5 +493,35 @@ pub const extern "C" fn is_prime_wc(x: u64) -> bool {
         debug_assert!(x&1==1);
     }
 
+    if x < 0x100000000 {
+        return crate::narrow::is_prime_wc_32(x as u32);
+    }
+
     core_primality(x)
 }
+
+// The tiny build without QFT asserts in mul_inv2 above 2^32
+#[cfg(all(test, any(feature = "lucas", feature = "ssmr", feature = "qft")))]
+mod tests {
+    use super::{is_prime, is_prime_wc};
+    use crate::qft_64::is_prime_qft;
+
+    #[test]
+    fn agrees_with_qft() {
+        // Strong pseudoprime to the bases 2 to 23
+        assert!(!is_prime(3825123056546413051));
+        assert!(!is_prime_wc(3825123056546413051));
+        assert!(is_prime(18446744073709551557));
+        assert!(is_prime_wc(18446744073709551557));
+        let mut x = [0u64; 20000];
+        let mut out = [false; 20000];
+        for (i, v) in x.iter_mut().enumerate() {
+            *v = (1u64 << 40) + i as u64;
+        }
+        crate::batch::is_prime_batch(&x, &mut out);
+        for (&v, &o) in x.iter().zip(out.iter()) {
+            assert_eq!(is_prime(v), is_prime_qft(v));
+            assert_eq!(is_prime(v), o);
+        }
+    }
+}
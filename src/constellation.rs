@@ -0,0 +1,300 @@
+use core::iter::FusedIterator;
+
+use crate::check::{is_prime, is_prime_wc};
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wide::is_prime_wc_128;
+
+/*
+   Prime constellations
+
+   A pattern of offsets 0 = o_0 < o_1 < ... < o_{k-1} is admissible if for every prime p the offsets do not cover
+   every residue class modulo p, otherwise one of n + o_i is always divisible by p. Only p <= k needs to be checked.
+
+   Candidates n are sieved in blocks, clearing n whenever n + o_i has a factor among 2 and the 128 odd primes of the
+   trial division tables (3 through 727). The survivors, under 1/40 of the candidates for twins and under 1/900 for
+   quadruplets, are confirmed by is_prime_wc at each offset.
+*/
+
+/// Number of candidates sieved at a time
+const BLOCK_BITS: usize = 1 << 14;
+
+const BLOCK_WORDS: usize = BLOCK_BITS / 64;
+
+/// Greatest sieving prime, any value that survives the sieve above it has no factor <= SIEVE_MAX
+pub(crate) const SIEVE_MAX: u64 = 727;
+
+const fn sieving_primes() -> [u16; 129] {
+    let mut primes = [0u16; 129];
+    let mut idx = 0;
+    let mut n = 2u16;
+    while idx < 129 {
+        if is_prime(n as u64) {
+            primes[idx] = n;
+            idx += 1;
+        }
+        n += 1;
+    }
+    primes
+}
+
+/// 2 and the first 128 odd primes
+pub(crate) const SIEVING_PRIMES: [u16; 129] = sieving_primes();
+
+/// Pattern of offsets of a prime constellation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Constellation<const K: usize> {
+    offsets: [u64; K],
+}
+
+/// Twin primes (p, p+2)
+pub const TWIN_PRIMES: Constellation<2> = Constellation { offsets: [0, 2] };
+
+/// Cousin primes (p, p+4)
+pub const COUSIN_PRIMES: Constellation<2> = Constellation { offsets: [0, 4] };
+
+/// Sexy primes (p, p+6)
+pub const SEXY_PRIMES: Constellation<2> = Constellation { offsets: [0, 6] };
+
+/// Prime triplets (p, p+2, p+6)
+pub const PRIME_TRIPLETS: Constellation<3> = Constellation { offsets: [0, 2, 6] };
+
+/// Prime triplets (p, p+4, p+6)
+pub const PRIME_TRIPLETS_ALT: Constellation<3> = Constellation { offsets: [0, 4, 6] };
+
+/// Prime quadruplets (p, p+2, p+6, p+8)
+pub const PRIME_QUADRUPLETS: Constellation<4> = Constellation { offsets: [0, 2, 6, 8] };
+
+impl<const K: usize> Constellation<K> {
+    /// Constellation from its offsets
+    ///
+    /// In: Offsets starting at 0 in increasing order
+    ///
+    /// Out: None if the offsets are not strictly increasing from 0 or the pattern is not admissible
+    pub const fn new(offsets: [u64; K]) -> Option<Self> {
+        if K == 0 || offsets[0] != 0 {
+            return None;
+        }
+        let mut idx = 1;
+        while idx < K {
+            if offsets[idx] <= offsets[idx - 1] {
+                return None;
+            }
+            idx += 1;
+        }
+        if !is_admissible(&offsets) {
+            return None;
+        }
+        Some(Self { offsets })
+    }
+
+    /// Offsets of the pattern
+    pub const fn offsets(&self) -> &[u64; K] {
+        &self.offsets
+    }
+
+    /// Width of the pattern, its greatest offset
+    pub const fn width(&self) -> u64 {
+        self.offsets[K - 1]
+    }
+
+    /// Bases of the constellation in an interval
+    ///
+    /// In: [lo,hi)
+    ///
+    /// Out: Iterator over p in [lo,hi) such that p + o is prime for every offset o
+    pub const fn search(self, lo: u64, hi: u64) -> Constellations<K> {
+        Constellations { pattern: self, lo: lo as u128, hi: hi as u128, block: Block::EMPTY }
+    }
+
+    /// Bases of the constellation in an interval, 128-bit form
+    ///
+    /// In: [lo,hi)
+    ///
+    /// Out: Iterator over p in [lo,hi) such that p + o is prime for every offset o
+    #[cfg(any(feature = "wide", feature = "qft"))]
+    pub const fn search_128(self, lo: u128, hi: u128) -> Constellations128<K> {
+        Constellations128 { pattern: self, lo, hi, block: Block::EMPTY }
+    }
+
+    // In: Candidate that survived the sieve, primality test for values above the sieving primes
+    // Out: n + o is prime for every offset
+    #[inline]
+    fn confirm(&self, n: u128, test: impl Fn(u128) -> bool) -> bool {
+        self.offsets.iter().all(|&o| {
+            let v = n + o as u128;
+            if v <= SIEVE_MAX as u128 {
+                is_prime(v as u64)
+            } else {
+                test(v)
+            }
+        })
+    }
+}
+
+/// Admissibility of a pattern of offsets
+///
+/// In: Offsets
+///
+/// Out: True if for every prime p the offsets miss at least one residue class modulo p
+pub const fn is_admissible(offsets: &[u64]) -> bool {
+    let k = offsets.len() as u64;
+    let mut p = 2;
+    // k offsets cover at most k residue classes
+    while p <= k {
+        if is_prime(p) {
+            let mut r = 0;
+            let mut covered = true;
+            while r < p && covered {
+                let mut idx = 0;
+                let mut hit = false;
+                while idx < offsets.len() {
+                    if offsets[idx] % p == r {
+                        hit = true;
+                    }
+                    idx += 1;
+                }
+                covered = hit;
+                r += 1;
+            }
+            if covered {
+                return false;
+            }
+        }
+        p += 1;
+    }
+    true
+}
+
+// Sieved block of candidates [start,start+len), bit i is set if start + i survives
+#[derive(Clone, Debug)]
+struct Block {
+    start: u128,
+    len: usize,
+    // Next bit to examine
+    pos: usize,
+    bits: [u64; BLOCK_WORDS],
+}
+
+impl Block {
+    const EMPTY: Self = Self { start: 0, len: 0, pos: 0, bits: [0; BLOCK_WORDS] };
+
+    // In: Offsets, start, len <= BLOCK_BITS, greatest value that n + o may take
+    fn sieve<const K: usize>(offsets: &[u64; K], start: u128, len: usize, max: u128) -> Self {
+        let mut block = Self { start, len, pos: 0, bits: [u64::MAX; BLOCK_WORDS] };
+        let words = len.div_ceil(64);
+        if len & 63 != 0 {
+            block.bits[words - 1] = (1u64 << (len & 63)) - 1;
+        }
+        block.bits[words..].fill(0);
+
+        // Candidates n with n + width > max are excluded
+        let width = offsets[K - 1] as u128;
+        let last = start + (len as u128 - 1);
+        if last.saturating_add(width) > max {
+            let first = (max - width).saturating_add(1).max(start);
+            for i in (first - start) as usize..len {
+                block.bits[i >> 6] &= !(1 << (i & 63));
+            }
+        }
+
+        for &p in SIEVING_PRIMES.iter() {
+            let p = p as u128;
+            for &o in offsets.iter() {
+                // Least n >= start with p | n + o, excluding n + o = p itself
+                let r = (start % p + o as u128 % p) % p;
+                let mut i = ((p - r) % p) as usize;
+                while (start + i as u128).saturating_add(o as u128) < 2 * p {
+                    i += p as usize;
+                }
+                while i < len {
+                    block.bits[i >> 6] &= !(1 << (i & 63));
+                    i += p as usize;
+                }
+            }
+        }
+        block
+    }
+
+    // Out: Next surviving candidate
+    #[inline]
+    fn next_survivor(&mut self) -> Option<u128> {
+        while self.pos < self.len {
+            let word = self.bits[self.pos >> 6] >> (self.pos & 63);
+            if word == 0 {
+                self.pos = (self.pos | 63) + 1;
+                continue;
+            }
+            let i = self.pos + word.trailing_zeros() as usize;
+            self.pos = i + 1;
+            return Some(self.start + i as u128);
+        }
+        None
+    }
+}
+
+/// Iterator over the bases of a prime constellation in an interval of 64-bit integers
+#[derive(Clone, Debug)]
+pub struct Constellations<const K: usize> {
+    pattern: Constellation<K>,
+    // Candidates [lo,hi) that have not been sieved
+    lo: u128,
+    hi: u128,
+    block: Block,
+}
+
+impl<const K: usize> Iterator for Constellations<K> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            while let Some(n) = self.block.next_survivor() {
+                if self.pattern.confirm(n, |v| is_prime_wc(v as u64)) {
+                    return Some(n as u64);
+                }
+            }
+            if self.lo >= self.hi {
+                return None;
+            }
+            let len = (self.hi - self.lo).min(BLOCK_BITS as u128) as usize;
+            self.block = Block::sieve(&self.pattern.offsets, self.lo, len, u64::MAX as u128);
+            self.lo += len as u128;
+        }
+    }
+}
+
+impl<const K: usize> FusedIterator for Constellations<K> {}
+
+/// Iterator over the bases of a prime constellation in an interval of 128-bit integers
+#[cfg(any(feature = "wide", feature = "qft"))]
+#[derive(Clone, Debug)]
+pub struct Constellations128<const K: usize> {
+    pattern: Constellation<K>,
+    lo: u128,
+    hi: u128,
+    block: Block,
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl<const K: usize> Iterator for Constellations128<K> {
+    type Item = u128;
+
+    fn next(&mut self) -> Option<u128> {
+        loop {
+            while let Some(n) = self.block.next_survivor() {
+                if self.pattern.confirm(n, |v| is_prime_wc_128(v)) {
+                    return Some(n);
+                }
+            }
+            if self.lo >= self.hi {
+                return None;
+            }
+            let len = (self.hi - self.lo).min(BLOCK_BITS as u128) as usize;
+            self.block = Block::sieve(&self.pattern.offsets, self.lo, len, u128::MAX);
+            self.lo += len as u128;
+        }
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl<const K: usize> FusedIterator for Constellations128<K> {}
@@ -0,0 +1,250 @@
+use crate::check::{mont_pow, mul_inv2_newton, one_mont, signed_mod, strong_fermat, to_mont, two_mont};
+use crate::factor::gcd;
+use crate::lucas_test::strong;
+use crate::qft_64::qft_64;
+use crate::symbol::jacobi;
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::factor_128::gcd_128;
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::lucas_test::strong_128;
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::qft::qft;
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::symbol::jacobi_128;
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wide::{mont_pow_128, mul_inv2_128, one_mont_128, signed_mod_128, strong_fermat_128, to_mont_128, two_mont_128};
+
+/*
+   Primality test contexts
+
+   A context holds the values every test of N shares, N^-1 over Z/2^k, the trailing zeros of N-1 and Mont(1,N),
+   Mont(2,N), Mont(N-1,N), so that a set of witnesses only pays for the exponentiations.
+
+   A base divisible by N gives no information about N and passes the Fermat, Euler and strong tests, as in the
+   usual presentation of deterministic witness sets, e.g bases 2, 3, 5, ..., 37 for all N < 2^64.
+*/
+
+/// Precomputed values for testing an odd N < 2^64
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrimalityContext {
+    n: u64,
+    tz: u32,
+    inv: u64,
+    one: u64,
+    two: u64,
+    oneinv: u64,
+}
+
+impl PrimalityContext {
+    /// Context for an odd N
+    ///
+    /// Out: None if N is even or N < 3
+    pub const fn new(n: u64) -> Option<Self> {
+        if n & 1 == 0 || n < 3 {
+            return None;
+        }
+        let one = one_mont(n);
+        Some(Self { n, tz: (n - 1).trailing_zeros(), inv: mul_inv2_newton(n), one, two: two_mont(one, n), oneinv: n - one })
+    }
+
+    /// N
+    pub const fn n(&self) -> u64 {
+        self.n
+    }
+
+    /// Strong probable prime test
+    ///
+    /// Out: SPRP(N,base), true if N divides the base
+    pub const fn sprp(&self, base: u64) -> bool {
+        let base = base % self.n;
+        if base == 0 {
+            return true;
+        }
+        strong_fermat(self.n, self.tz, to_mont(base, self.n), self.one, self.oneinv, self.inv)
+    }
+
+    /// Strong probable prime test to each base
+    ///
+    /// Out: True if N is a strong probable prime to every base
+    pub const fn sprp_bases(&self, bases: &[u64]) -> bool {
+        let mut idx = 0;
+        while idx < bases.len() {
+            if !self.sprp(bases[idx]) {
+                return false;
+            }
+            idx += 1;
+        }
+        true
+    }
+
+    /// Fermat probable prime test
+    ///
+    /// Out: base^(N-1) ≡ 1 (mod N), true if N divides the base
+    pub const fn fermat(&self, base: u64) -> bool {
+        let base = base % self.n;
+        if base == 0 {
+            return true;
+        }
+        mont_pow(to_mont(base, self.n), self.one, self.n - 1, self.inv, self.n) == self.one
+    }
+
+    /// Euler-Jacobi probable prime test
+    ///
+    /// Out: base^((N-1)/2) ≡ Jacobi(base,N) (mod N), true if N divides the base
+    pub const fn euler(&self, base: u64) -> bool {
+        let base = base % self.n;
+        if base == 0 {
+            return true;
+        }
+        let expected = match jacobi(base, self.n) {
+            1 => self.one,
+            -1 => self.oneinv,
+            _ => return false,
+        };
+        mont_pow(to_mont(base, self.n), self.one, self.n >> 1, self.inv, self.n) == expected
+    }
+
+    /// Strong Lucas probable prime test with parameters P,Q
+    ///
+    /// Out: True if N is a strong Lucas probable prime with D = P^2 - 4Q, false if gcd(QD,N) != 1
+    pub const fn lucas(&self, p: i64, q: i64) -> bool {
+        let n = self.n;
+        let d = signed_mod(p as i128 * p as i128 - 4 * q as i128, n);
+        let q = signed_mod(q as i128, n);
+        let m = match jacobi(d, n) {
+            1 => n - 1,
+            // 2^64-1 is composite
+            -1 if n != u64::MAX => n + 1,
+            _ => return false,
+        };
+        if gcd(q, n) != 1 {
+            return false;
+        }
+        strong(n, self.inv, self.one, m, signed_mod(p as i128, n), q, d)
+    }
+
+    /// Khashin's Quadratic Frobenius test
+    ///
+    /// Out: QFT(N), false if N is a square
+    pub const fn qft(&self) -> bool {
+        let sqrt = self.n.isqrt();
+        if sqrt * sqrt == self.n {
+            return false;
+        }
+        qft_64(self.n, self.one, self.two, self.oneinv, self.inv)
+    }
+}
+
+/// Precomputed values for testing an odd N < 2^128
+#[cfg(any(feature = "wide", feature = "qft"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrimalityContext128 {
+    n: u128,
+    tz: u32,
+    inv: u128,
+    one: u128,
+    two: u128,
+    oneinv: u128,
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl PrimalityContext128 {
+    /// Context for an odd N
+    ///
+    /// Out: None if N is even or N < 3
+    pub const fn new(n: u128) -> Option<Self> {
+        if n & 1 == 0 || n < 3 {
+            return None;
+        }
+        let one = one_mont_128(n);
+        Some(Self { n, tz: (n - 1).trailing_zeros(), inv: mul_inv2_128(n), one, two: two_mont_128(one, n), oneinv: n - one })
+    }
+
+    /// N
+    pub const fn n(&self) -> u128 {
+        self.n
+    }
+
+    /// Strong probable prime test
+    ///
+    /// Out: SPRP(N,base), true if N divides the base
+    pub const fn sprp(&self, base: u128) -> bool {
+        let base = base % self.n;
+        if base == 0 {
+            return true;
+        }
+        strong_fermat_128(self.n, self.tz, to_mont_128(base, self.n), self.one, self.oneinv, self.inv)
+    }
+
+    /// Strong probable prime test to each base
+    ///
+    /// Out: True if N is a strong probable prime to every base
+    pub const fn sprp_bases(&self, bases: &[u128]) -> bool {
+        let mut idx = 0;
+        while idx < bases.len() {
+            if !self.sprp(bases[idx]) {
+                return false;
+            }
+            idx += 1;
+        }
+        true
+    }
+
+    /// Fermat probable prime test
+    ///
+    /// Out: base^(N-1) ≡ 1 (mod N), true if N divides the base
+    pub const fn fermat(&self, base: u128) -> bool {
+        let base = base % self.n;
+        if base == 0 {
+            return true;
+        }
+        mont_pow_128(to_mont_128(base, self.n), self.one, self.n - 1, self.inv, self.n) == self.one
+    }
+
+    /// Euler-Jacobi probable prime test
+    ///
+    /// Out: base^((N-1)/2) ≡ Jacobi(base,N) (mod N), true if N divides the base
+    pub const fn euler(&self, base: u128) -> bool {
+        let base = base % self.n;
+        if base == 0 {
+            return true;
+        }
+        let expected = match jacobi_128(base, self.n) {
+            1 => self.one,
+            -1 => self.oneinv,
+            _ => return false,
+        };
+        mont_pow_128(to_mont_128(base, self.n), self.one, self.n >> 1, self.inv, self.n) == expected
+    }
+
+    /// Strong Lucas probable prime test with parameters P,Q
+    ///
+    /// Out: True if N is a strong Lucas probable prime with D = P^2 - 4Q, false if gcd(QD,N) != 1
+    pub const fn lucas(&self, p: i64, q: i64) -> bool {
+        let n = self.n;
+        let d = signed_mod_128(p as i128 * p as i128 - 4 * q as i128, n);
+        let q = signed_mod_128(q as i128, n);
+        let m = match jacobi_128(d, n) {
+            1 => n - 1,
+            // 2^128-1 is composite
+            -1 if n != u128::MAX => n + 1,
+            _ => return false,
+        };
+        if gcd_128(q, n) != 1 {
+            return false;
+        }
+        strong_128(n, self.inv, self.one, m, signed_mod_128(p as i128, n), q, d)
+    }
+
+    /// Khashin's Quadratic Frobenius test
+    ///
+    /// Out: QFT(N), false if N is a square
+    pub const fn qft(&self) -> bool {
+        let sqrt = self.n.isqrt();
+        if sqrt * sqrt == self.n {
+            return false;
+        }
+        qft(self.n, self.one, self.two, self.oneinv, self.inv)
+    }
+}
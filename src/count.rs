@@ -0,0 +1,780 @@
+use crate::iter::pi_upper;
+use crate::iter::Primes;
+use crate::sieve::{prime_bitmap, SievingPrimes};
+
+/*
+   Prime counting and prime sums
+
+   pi(x) and the sum of the primes up to x are evaluated with the Lagarias-Miller-Odlyzko algorithm, using
+   a single implementation that is generic over the weight w(n) given to each integer, w(n) = 1 for counting and
+   w(n) = n for summing. Both weights are completely multiplicative which is all that the algorithm requires.
+
+   With y >= cbrt(x) and a = pi(y)
+
+     W(x) = phi(x,a) + W(p_a) - 1 - P2(x,a)
+
+   where W(x) is the weight of the primes up to x, phi(x,a) the weight of the integers up to x free of the first a primes
+   and P2(x,a) the weight of the integers with exactly two prime factors, both greater than p_a.
+
+   phi(x,a) is expanded as the ordinary leaves  sum mu(n)*w(n)*phi(x/n,c) over n <= y with no prime factor <= p_c, where
+   phi(v,c) is evaluated from a table modulo 2*3*5*7*11, and the special leaves -mu(m)*w(m*p_b)*phi(x/(m*p_b),b-1) over
+   m <= y < m*p_b with no prime factor <= p_b. The special leaves are evaluated by sieving [1,x/y] in segments, with
+   a Fenwick tree over the segment giving the weight of the unsieved integers up to x/(m*p_b).
+
+   P2(x,a) is the sum of w(p)*(W(x/p) - W(p-1)) over y < p <= sqrt(x). W(x/p) is evaluated by sieving (sqrt(x),x/y]
+   downwards with prime_bitmap while the primes p are taken in increasing order.
+
+   The workspace holds the mobius function and least prime factor of the integers up to y, the primes up to y, the
+   per-prime weights carried between segments, and the Fenwick tree and bitmap of the current segment. Increasing y
+   reduces the sieving interval at the cost of more special leaves; y = alpha*cbrt(x) with alpha growing slowly
+   with x is a close balance. The running time is roughly O(x^(2/3)), pi(10^15) takes under a minute and pi(10^18) about an hour.
+*/
+
+/// Product of the primes 2..=11, modulus of the phi table
+const Q: usize = 2310;
+
+/// Number of primes in the phi table
+const C: usize = 5;
+
+/// Integers below this are counted with prime_bitmap directly
+const DIRECT_BOUND: u64 = 1 << 20;
+
+/// Segment length of the sieve, capped to limit the workspace
+const MAX_SEGMENT: u64 = 1 << 22;
+
+/// pi(2^64-1), the number of 64-bit primes
+const PI_MAX: u64 = 425656284035217743;
+
+/// 2^64-59, the largest 64-bit prime
+const LARGEST_PRIME: u64 = 0xFFFFFFFFFFFFFFC5;
+
+/// Words of workspace used by the local sieve of nth_prime
+const LOCAL_WORDS: usize = 1 << 15;
+
+// Out: Number of integers in [1,r] coprime to Q, for r < Q
+const fn phi_count_table() -> [u16; Q] {
+    let mut table = [0u16; Q];
+    let mut count = 0;
+    let mut r = 1;
+    while r < Q {
+        if r % 2 != 0 && r % 3 != 0 && r % 5 != 0 && r % 7 != 0 && r % 11 != 0 {
+            count += 1;
+        }
+        table[r] = count;
+        r += 1;
+    }
+    table
+}
+
+// Out: Sum of the integers in [1,r] coprime to Q, for r < Q
+const fn phi_sum_table() -> [u32; Q] {
+    let mut table = [0u32; Q];
+    let mut sum = 0;
+    let mut r = 1;
+    while r < Q {
+        if r % 2 != 0 && r % 3 != 0 && r % 5 != 0 && r % 7 != 0 && r % 11 != 0 {
+            sum += r as u32;
+        }
+        table[r] = sum;
+        r += 1;
+    }
+    table
+}
+
+static PHI_COUNT: [u16; Q] = phi_count_table();
+
+static PHI_SUM: [u32; Q] = phi_sum_table();
+
+/// Weight of an integer, wrapping arithmetic throughout as the leaves carry signs
+trait Weight: Copy {
+    /// Words of the workspace used to store one weight
+    const WORDS: usize;
+
+    fn zero() -> Self;
+
+    fn of(n: u64) -> Self;
+
+    fn add(self, rhs: Self) -> Self;
+
+    fn sub(self, rhs: Self) -> Self;
+
+    fn mul(self, rhs: Self) -> Self;
+
+    /// phi(v,C), the weight of the integers in [1,v] coprime to Q
+    fn phi_small(v: u64) -> Self;
+
+    /// Weight of the set bits of a word, bit i represents base + i
+    fn bits(word: u64, base: u64) -> Self;
+
+    fn load(ws: &[u64], idx: usize) -> Self;
+
+    fn store(ws: &mut [u64], idx: usize, value: Self);
+}
+
+impl Weight for u64 {
+    const WORDS: usize = 1;
+
+    #[inline]
+    fn zero() -> Self {
+        0
+    }
+
+    #[inline]
+    fn of(_: u64) -> Self {
+        1
+    }
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        self.wrapping_add(rhs)
+    }
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        self.wrapping_sub(rhs)
+    }
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        self.wrapping_mul(rhs)
+    }
+
+    #[inline]
+    fn phi_small(v: u64) -> Self {
+        let q = Q as u64;
+        (v / q) * PHI_COUNT[Q - 1] as u64 + PHI_COUNT[(v % q) as usize] as u64
+    }
+
+    #[inline]
+    fn bits(word: u64, _: u64) -> Self {
+        word.count_ones() as u64
+    }
+
+    #[inline]
+    fn load(ws: &[u64], idx: usize) -> Self {
+        ws[idx]
+    }
+
+    #[inline]
+    fn store(ws: &mut [u64], idx: usize, value: Self) {
+        ws[idx] = value;
+    }
+}
+
+impl Weight for u128 {
+    const WORDS: usize = 2;
+
+    #[inline]
+    fn zero() -> Self {
+        0
+    }
+
+    #[inline]
+    fn of(n: u64) -> Self {
+        n as u128
+    }
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        self.wrapping_add(rhs)
+    }
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        self.wrapping_sub(rhs)
+    }
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        self.wrapping_mul(rhs)
+    }
+
+    #[inline]
+    fn phi_small(v: u64) -> Self {
+        // v = t*Q + r, each full block [jQ+1,(j+1)Q] contributes jQ*phi(Q) + S where S is the sum of the residues
+        let q = Q as u128;
+        let (t, r) = ((v / Q as u64) as u128, (v % Q as u64) as usize);
+        let count = PHI_COUNT[Q - 1] as u128;
+        let residues = PHI_SUM[Q - 1] as u128;
+        let full = q * count * (t * t.saturating_sub(1) / 2) + t * residues;
+        full + t * q * PHI_COUNT[r] as u128 + PHI_SUM[r] as u128
+    }
+
+    #[inline]
+    fn bits(mut word: u64, base: u64) -> Self {
+        let mut sum = (word.count_ones() as u128) * base as u128;
+        while word != 0 {
+            sum += word.trailing_zeros() as u128;
+            word &= word - 1;
+        }
+        sum
+    }
+
+    #[inline]
+    fn load(ws: &[u64], idx: usize) -> Self {
+        ws[2 * idx] as u128 | (ws[2 * idx + 1] as u128) << 64
+    }
+
+    #[inline]
+    fn store(ws: &mut [u64], idx: usize, value: Self) {
+        ws[2 * idx] = value as u64;
+        ws[2 * idx + 1] = (value >> 64) as u64;
+    }
+}
+
+// In: X
+// Out: floor(cbrt(X))
+fn icbrt(x: u64) -> u64 {
+    if x < 8 {
+        return if x == 0 { 0 } else { 1 };
+    }
+    // Initial estimate is greater than the root, Newton's iteration then decreases monotonically
+    let mut r = 1u64 << (x.ilog2() / 3 + 1);
+    loop {
+        let next = (2 * r + x / (r * r)) / 3;
+        if next >= r {
+            return r;
+        }
+        r = next;
+    }
+}
+
+// Parameters of the algorithm for x >= DIRECT_BOUND
+struct Layout {
+    y: u64,
+    // Upper bound of pi(y)
+    a_max: usize,
+    // Segment length, a multiple of 64
+    segment: u64,
+}
+
+impl Layout {
+    fn new(x: u64) -> Self {
+        let alpha = (x.ilog2() as u64).saturating_sub(20) / 8 + 1;
+        let y = (alpha * icbrt(x)).min(x.isqrt());
+        let a_max = pi_upper(y as u128) as usize;
+        let segment = y.next_multiple_of(64).clamp(1 << 12, MAX_SEGMENT);
+        Self { y, a_max, segment }
+    }
+
+    // Words of the workspace for the weight with the most words
+    fn words(&self) -> usize {
+        let w = <u128 as Weight>::WORDS;
+        (self.y as usize + 1) + (self.a_max + 1) + (self.a_max + 1) * w + (self.segment as usize + 1) * w
+            + (self.segment as usize / 64)
+    }
+}
+
+// Mobius function and least prime factor packed as lpf << 2 | code, code 1 for mu = 1, 2 for mu = -1, 0 for mu = 0
+const MU_POS: u64 = 1;
+const MU_NEG: u64 = 2;
+
+// In: Y, table of Y + 1 words, primes of at least pi(Y) + 1 words
+// Out: Packed mobius and least prime factor of [1,Y], primes[1..=a] are the primes up to Y. Returns a
+fn small_tables(y: u64, mu_lpf: &mut [u64], primes: &mut [u64]) -> usize {
+    let y = y as usize;
+    mu_lpf[..=y].fill(0);
+    primes[0] = 0;
+    // 1 has no prime factors, its least prime factor is treated as infinite
+    mu_lpf[1] = (u64::MAX >> 2) << 2 | MU_POS;
+    let mut a = 0;
+
+    for n in 2..=y {
+        if mu_lpf[n] != 0 {
+            continue;
+        }
+        a += 1;
+        primes[a] = n as u64;
+
+        let mut k = n;
+        while k <= y {
+            let mut e = mu_lpf[k];
+            if e == 0 {
+                e = (n as u64) << 2 | MU_POS;
+            }
+            e = match e & 3 {
+                MU_POS => e ^ (MU_POS | MU_NEG),
+                MU_NEG => e ^ (MU_POS | MU_NEG),
+                _ => e,
+            };
+            mu_lpf[k] = e;
+            k += n;
+        }
+        if let Some(sqr) = n.checked_mul(n) {
+            let mut k = sqr;
+            while k <= y {
+                mu_lpf[k] &= !3;
+                k += sqr;
+            }
+        }
+    }
+    a
+}
+
+// Fenwick tree over positions 1..=len, stored from tree[W::WORDS]
+struct Fenwick<'a, W: Weight> {
+    tree: &'a mut [u64],
+    len: usize,
+    _weight: core::marker::PhantomData<W>,
+}
+
+impl<'a, W: Weight> Fenwick<'a, W> {
+    // In: Weights of positions 1..=len already stored in the tree
+    fn build(tree: &'a mut [u64], len: usize) -> Self {
+        for i in 1..=len {
+            let j = i + (i & i.wrapping_neg());
+            if j <= len {
+                let value = W::load(tree, j).add(W::load(tree, i));
+                W::store(tree, j, value);
+            }
+        }
+        Self { tree, len, _weight: core::marker::PhantomData }
+    }
+
+    #[inline]
+    fn remove(&mut self, mut i: usize, value: W) {
+        while i <= self.len {
+            let updated = W::load(self.tree, i).sub(value);
+            W::store(self.tree, i, updated);
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    // Out: Weight of positions 1..=i
+    #[inline]
+    fn prefix(&self, mut i: usize) -> W {
+        let mut sum = W::zero();
+        while i > 0 {
+            sum = sum.add(W::load(self.tree, i));
+            i &= i - 1;
+        }
+        sum
+    }
+}
+
+// In: Bitmap of [lo, lo + 64*bitmap.len()), [start,end) within it
+// Out: Weight of the set bits representing [start,end)
+fn range_weight<W: Weight>(bitmap: &[u64], lo: u64, start: u64, end: u64) -> W {
+    let mut sum = W::zero();
+    let (mut i, end) = (start - lo, end - lo);
+    while i < end {
+        let word = (i >> 6) as usize;
+        let offset = i & 63;
+        let take = (64 - offset).min(end - i);
+        let mask = if take == 64 { u64::MAX } else { ((1u64 << take) - 1) << offset };
+        sum = sum.add(W::bits(bitmap[word] & mask, lo + ((word as u64) << 6)));
+        i += take;
+    }
+    sum
+}
+
+// In: X, workspace of at least one word
+// Out: Weight of the primes up to X by sieving
+fn direct<W: Weight>(x: u64, ws: &mut [u64]) -> W {
+    let segment = 64 * ws.len() as u64;
+    let mut sum = W::zero();
+    let mut lo = 0;
+    while lo <= x {
+        let hi = if x - lo >= segment { lo + segment } else { x + 1 };
+        // The workspace always holds the segment
+        let _ = prime_bitmap(lo, hi, ws);
+        sum = sum.add(range_weight::<W>(ws, lo, lo, hi));
+        lo = hi;
+    }
+    sum
+}
+
+// In: X >= DIRECT_BOUND, workspace of at least Layout::words
+// Out: Weight of the primes up to X
+fn lmo<W: Weight>(x: u64, ws: &mut [u64]) -> W {
+    let layout = Layout::new(x);
+    let (y, segment) = (layout.y, layout.segment);
+
+    let (mu_lpf, rest) = ws.split_at_mut(y as usize + 1);
+    let (primes, rest) = rest.split_at_mut(layout.a_max + 1);
+    let (carry, rest) = rest.split_at_mut((layout.a_max + 1) * W::WORDS);
+    let (tree, bitmap) = rest.split_at_mut((segment as usize + 1) * W::WORDS);
+
+    let a = small_tables(y, mu_lpf, primes);
+    let primes = &primes[..=a];
+    let pc = primes[C];
+
+    // Ordinary leaves
+    let mut phi = W::zero();
+    for n in 1..=y {
+        let e = mu_lpf[n as usize];
+        if e >> 2 <= pc {
+            continue;
+        }
+        let leaf = W::of(n).mul(W::phi_small(x / n));
+        phi = match e & 3 {
+            MU_POS => phi.add(leaf),
+            MU_NEG => phi.sub(leaf),
+            _ => phi,
+        };
+    }
+
+    // Special leaves
+    carry[..(a + 1) * W::WORDS].fill(0);
+    let limit = x / y + 1;
+    let mut low = 1;
+
+    while low < limit {
+        let high = if limit - low > segment { low + segment } else { limit };
+        let len = (high - low) as usize;
+
+        let words = len.div_ceil(64);
+        bitmap[..words].fill(u64::MAX);
+        if len & 63 != 0 {
+            bitmap[words - 1] = (1u64 << (len & 63)) - 1;
+        }
+        let mut total = W::zero();
+        for i in 1..=len {
+            let value = W::of(low + i as u64 - 1);
+            total = total.add(value);
+            W::store(tree, i, value);
+        }
+        let mut fenwick = Fenwick::<W>::build(tree, len);
+
+        for b in 1..=a {
+            let p = primes[b];
+
+            if b > C {
+                // Leaves x/(m*p) in [low,high)
+                let m_hi = y.min(x / low / p);
+                let m_lo = (y / p).max(x / high / p);
+
+                if m_lo < m_hi {
+                    let carried = W::load(carry, b);
+                    let wp = W::of(p);
+                    if p * p > y {
+                        // m <= y < p^2 has no prime factor <= p so it is a prime greater than p
+                        let start = primes.partition_point(|&q| q <= m_lo.max(p));
+                        let end = primes.partition_point(|&q| q <= m_hi);
+                        for &m in &primes[start..end.max(start)] {
+                            let v = x / (m * p);
+                            let count = carried.add(fenwick.prefix((v - low + 1) as usize));
+                            phi = phi.add(W::of(m).mul(wp).mul(count));
+                        }
+                    } else {
+                        for m in m_lo + 1..=m_hi {
+                            let e = mu_lpf[m as usize];
+                            if e >> 2 <= p {
+                                continue;
+                            }
+                            let v = x / (m * p);
+                            let leaf = W::of(m).mul(wp).mul(carried.add(fenwick.prefix((v - low + 1) as usize)));
+                            phi = match e & 3 {
+                                MU_POS => phi.sub(leaf),
+                                MU_NEG => phi.add(leaf),
+                                _ => phi,
+                            };
+                        }
+                    }
+                }
+            }
+
+            // Weight of [1,high) free of the first b - 1 primes
+            let carried = W::load(carry, b).add(total);
+            W::store(carry, b, carried);
+
+            if b == a {
+                break;
+            }
+            let mut k = low.div_ceil(p) * p;
+            while k < high {
+                let j = k - low;
+                let bit = 1u64 << (j & 63);
+                let word = &mut bitmap[(j >> 6) as usize];
+                if *word & bit != 0 {
+                    *word &= !bit;
+                    let value = W::of(k);
+                    fenwick.remove(j as usize + 1, value);
+                    total = total.sub(value);
+                }
+                k += p;
+            }
+        }
+        low = high;
+    }
+
+    // Weight of the primes up to y
+    let mut small = W::zero();
+    for &p in &primes[1..] {
+        small = small.add(W::of(p));
+    }
+
+    let p2 = p2::<W>(x, y, small, segment, bitmap);
+
+    phi.add(small).sub(W::of(1)).sub(p2)
+}
+
+// In: X, Y, weight of the primes up to Y, bitmap of segment/64 words
+// Out: P2(x,a), the weight of the integers p*q <= x with y < p <= q
+fn p2<W: Weight>(x: u64, y: u64, small: W, segment: u64, bitmap: &mut [u64]) -> W {
+    let sqrt = x.isqrt();
+    if y >= sqrt {
+        return W::zero();
+    }
+    let top = x / (y + 1);
+    let floor = sqrt + 1;
+
+    // Primes in [pos,top] have been swept and have weight swept, the bitmap holds [seg_lo,pos)
+    let mut seg_lo = top + 1;
+    let mut pos = top + 1;
+    let mut swept = W::zero();
+
+    // Weight of the primes p-1 and below
+    let mut below = small;
+    let (mut sum_p, mut sum_upper, mut sum_lower) = (W::zero(), W::zero(), W::zero());
+
+    for p in SievingPrimes::new(sqrt) {
+        if p <= y {
+            continue;
+        }
+        let wp = W::of(p);
+        // Weight of the primes in (x/p,top]
+        let target = x / p + 1;
+        while target < seg_lo {
+            swept = swept.add(range_weight::<W>(bitmap, seg_lo, seg_lo, pos));
+            let seg_hi = seg_lo;
+            seg_lo = if seg_hi - floor > segment { seg_hi - segment } else { floor };
+            let _ = prime_bitmap(seg_lo, seg_hi, bitmap);
+            pos = seg_hi;
+        }
+        swept = swept.add(range_weight::<W>(bitmap, seg_lo, target, pos));
+        pos = target;
+
+        sum_p = sum_p.add(wp);
+        sum_upper = sum_upper.add(wp.mul(swept));
+        sum_lower = sum_lower.add(wp.mul(below));
+        below = below.add(wp);
+    }
+
+    // Complete the sweep for W(top) = W(sqrt) + weight of (sqrt,top]
+    swept = swept.add(range_weight::<W>(bitmap, seg_lo, seg_lo, pos));
+    while seg_lo > floor {
+        let seg_hi = seg_lo;
+        seg_lo = if seg_hi - floor > segment { seg_hi - segment } else { floor };
+        let _ = prime_bitmap(seg_lo, seg_hi, bitmap);
+        swept = swept.add(range_weight::<W>(bitmap, seg_lo, seg_lo, seg_hi));
+    }
+    let w_top = below.add(swept);
+
+    w_top.mul(sum_p).sub(sum_upper).sub(sum_lower)
+}
+
+/// Workspace required by prime_pi and prime_sum
+///
+/// In: X
+///
+/// Out: Number of words of workspace required to evaluate pi(X) or the sum of the primes up to X.
+///      Grows as cbrt(X)*log(X), about 118 MB at 10^18 and 225 MB near 2^64
+pub fn prime_pi_workspace(x: u64) -> usize {
+    if x < DIRECT_BOUND {
+        return 1 << 10;
+    }
+    Layout::new(x).words()
+}
+
+/// Prime counting function
+///
+/// In: X, workspace of prime_pi_workspace(X) words
+///
+/// Out: pi(X), the number of primes less than or equal to X, None if the workspace is too small
+pub fn prime_pi(x: u64, workspace: &mut [u64]) -> Option<u64> {
+    if workspace.len() < prime_pi_workspace(x) {
+        return None;
+    }
+    if x < DIRECT_BOUND {
+        return Some(direct::<u64>(x, workspace));
+    }
+    Some(lmo::<u64>(x, workspace))
+}
+
+/// Sum of the primes
+///
+/// In: X, workspace of prime_pi_workspace(X) words
+///
+/// Out: Sum of the primes less than or equal to X, None if the workspace is too small. The sum is less than 2^128
+///      for every 64-bit X
+pub fn prime_sum(x: u64, workspace: &mut [u64]) -> Option<u128> {
+    if workspace.len() < prime_pi_workspace(x) {
+        return None;
+    }
+    if x < DIRECT_BOUND {
+        return Some(direct::<u128>(x, workspace));
+    }
+    Some(lmo::<u128>(x, workspace))
+}
+
+// In: X > 0
+// Out: ln(X), accurate to about 1E-12
+fn ln(x: f64) -> f64 {
+    // x = m*2^k with m in [1,2), ln(m) = 2*atanh((m-1)/(m+1))
+    let bits = x.to_bits();
+    let k = ((bits >> 52) & 0x7FF) as i64 - 1023;
+    let m = f64::from_bits((bits & ((1 << 52) - 1)) | (1023 << 52));
+    let z = (m - 1.0) / (m + 1.0);
+    let z2 = z * z;
+    let mut term = z;
+    let mut sum = 0.0;
+    let mut idx = 1.0;
+    while idx < 30.0 {
+        sum += term / idx;
+        term *= z2;
+        idx += 2.0;
+    }
+    k as f64 * core::f64::consts::LN_2 + 2.0 * sum
+}
+
+// In: X >= 2
+// Out: li(X) by Ramanujan's series
+fn li(x: f64) -> f64 {
+    const EULER_GAMMA: f64 = 0.5772156649015329;
+    let l = ln(x);
+    let mut sum = 0.0;
+    let mut inner = 0.0;
+    // (-1)^(k-1) * l^k / (k! * 2^(k-1))
+    let mut term = 1.0;
+    let mut k = 1;
+    while k < 200 {
+        term *= l / k as f64;
+        if k > 1 {
+            term /= -2.0;
+        }
+        if k & 1 == 1 {
+            inner += 1.0 / k as f64;
+        }
+        sum += term * inner;
+        k += 1;
+    }
+    EULER_GAMMA + ln(l) + ((x as u64).isqrt() as f64) * sum
+}
+
+// In: X >= 2^20
+// Out: Approximation of pi(X), the first terms of Riemann's R(X)
+fn riemann_r(x: f64) -> f64 {
+    let x_int = x as u64;
+    li(x) - li(x_int.isqrt() as f64) / 2.0 - li(icbrt(x_int) as f64) / 3.0
+}
+
+// In: 16 <= N <= PI_MAX
+// Out: Estimate of the Nth prime, Cipolla's expansion refined by Newton's method on R(x) = N
+fn nth_prime_estimate(n: u64) -> u64 {
+    let nf = n as f64;
+    let l = ln(nf);
+    let ll = ln(l);
+    let mut est = nf * (l + ll - 1.0 + (ll - 2.0) / l);
+    if est > 1048576.0 {
+        let mut iter = 0;
+        while iter < 4 {
+            est -= (riemann_r(est) - nf) * ln(est);
+            iter += 1;
+        }
+    }
+    // Clamped to the largest 64-bit prime so that pi(estimate) >= N and the sieve never passes 2^64
+    if est >= LARGEST_PRIME as f64 {
+        LARGEST_PRIME
+    } else {
+        est as u64
+    }
+}
+
+/// Workspace required by nth_prime
+///
+/// In: N
+///
+/// Out: Number of words of workspace required to evaluate the Nth prime
+pub fn nth_prime_workspace(n: u64) -> usize {
+    if !(16..=PI_MAX).contains(&n) {
+        return 1 << 10;
+    }
+    prime_pi_workspace(nth_prime_estimate(n)).max(LOCAL_WORDS)
+}
+
+/// Nth prime
+///
+/// In: N, workspace of nth_prime_workspace(N) words
+///
+/// Out: Nth prime counting from nth_prime(1) = 2, None if N = 0, N > pi(2^64) or the workspace is too small.
+///      The prime counting function is evaluated once at an estimate of the Nth prime, the remaining primes
+///      are located by sieving the interval between the estimate and the Nth prime
+pub fn nth_prime(n: u64, workspace: &mut [u64]) -> Option<u64> {
+    if n == 0 || n > PI_MAX || workspace.len() < nth_prime_workspace(n) {
+        return None;
+    }
+    if n < 16 {
+        return Primes::new(0, 64).nth(n as usize - 1);
+    }
+    let x = nth_prime_estimate(n);
+    let count = prime_pi(x, workspace)?;
+
+    let bitmap = &mut workspace[..LOCAL_WORDS];
+    let segment = 64 * LOCAL_WORDS as u64;
+
+    if count < n {
+        // The (n - count)th prime after x
+        let mut remaining = n - count;
+        let mut lo = x + 1;
+        loop {
+            let hi = lo.saturating_add(segment);
+            if hi == lo {
+                return None;
+            }
+            let found = prime_bitmap(lo, hi, bitmap)?;
+            if found >= remaining {
+                return Some(lo + select_bit(bitmap, remaining));
+            }
+            remaining -= found;
+            lo = hi;
+        }
+    } else {
+        // The (count - n + 1)th prime counting down from x
+        let mut remaining = count - n + 1;
+        let mut hi = x + 1;
+        loop {
+            let lo = hi.saturating_sub(segment);
+            if lo == hi {
+                return None;
+            }
+            let found = prime_bitmap(lo, hi, bitmap)?;
+            if found >= remaining {
+                return Some(lo + select_bit(bitmap, found - remaining + 1));
+            }
+            remaining -= found;
+            hi = lo;
+        }
+    }
+}
+
+// In: Bitmap with at least K > 0 set bits
+// Out: Position of the Kth set bit
+fn select_bit(bitmap: &[u64], mut k: u64) -> u64 {
+    for (idx, &word) in bitmap.iter().enumerate() {
+        let ones = word.count_ones() as u64;
+        if ones < k {
+            k -= ones;
+            continue;
+        }
+        let mut bits = word;
+        while k > 1 {
+            bits &= bits - 1;
+            k -= 1;
+        }
+        return ((idx as u64) << 6) + bits.trailing_zeros() as u64;
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{nth_prime_estimate, LARGEST_PRIME, PI_MAX};
+
+    #[test]
+    fn estimate_below_2_64() {
+        assert_eq!(nth_prime_estimate(PI_MAX), LARGEST_PRIME);
+        assert!(nth_prime_estimate(PI_MAX - 10_000_000) <= LARGEST_PRIME);
+        assert!(nth_prime_estimate(PI_MAX / 2) < LARGEST_PRIME);
+    }
+}
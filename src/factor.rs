@@ -0,0 +1,437 @@
+use crate::check::{is_prime_wc, mont_prod, mont_sub, mul_inv2, one_mont};
+
+#[cfg(any(feature = "lucas", feature = "ssmr"))]
+use crate::primes::PRIME_TABLE;
+
+#[cfg(not(any(feature = "lucas", feature = "ssmr")))]
+use crate::constellation::SIEVING_PRIMES;
+
+/*
+   Factorisation of 64-bit integers
+
+   Trial division by the 128 odd primes of PRIME_TABLE removes every factor up to 727, using the same inverse
+   multiplication as is_prime, an exact quotient is then N*p^-1 over Z/2^64. A cofactor below 727^2 is prime.
+
+   Larger cofactors are split with Brent's variant of Pollard's rho in Montgomery form, accumulating 128 differences
+   before each gcd. Rho finds a factor p in about sqrt(p) iterations of a few nanoseconds each. Shanks' square forms
+   factorisation (SQUFOF) takes about N^(1/4) iterations regardless of the factors, but each costs a division and it is
+   slower than rho at every size, so it is only used when a rho cycle closes without a factor, before the polynomial is
+   changed. is_prime_wc decides when a cofactor is prime.
+
+   The product of the first 16 primes exceeds 2^64 so no 64-bit integer has more than 15 distinct prime factors.
+*/
+
+/// Maximum number of distinct prime factors of a 64-bit integer
+pub const FACTOR_CAPACITY: usize = 15;
+
+/// Greatest trial division prime
+const TRIAL_MAX: u64 = 727;
+
+/// SQUFOF multipliers, products of distinct small odd primes
+const SQUFOF_MULTIPLIERS: [u64; 16] = [
+    1, 3, 5, 7, 11, 15, 21, 33, 35, 55, 77, 105, 165, 231, 385, 1155,
+];
+
+// Bit r is set if r is a square modulo 64
+const SQUARE_MOD_64: u64 = {
+    let mut mask = 0u64;
+    let mut r = 0;
+    while r < 64 {
+        mask |= 1 << ((r * r) & 63);
+        r += 1;
+    }
+    mask
+};
+
+/// Prime factorisation of a 64-bit integer
+///
+/// Primes are stored in increasing order with their exponents. The factorisation of 0 and 1 is empty.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Factorization {
+    n: u64,
+    len: usize,
+    prime: [u64; FACTOR_CAPACITY],
+    exponent: [u8; FACTOR_CAPACITY],
+}
+
+impl Factorization {
+    const fn empty(n: u64) -> Self {
+        Self { n, len: 0, prime: [0; FACTOR_CAPACITY], exponent: [0; FACTOR_CAPACITY] }
+    }
+
+    /// The integer that was factored
+    pub const fn n(&self) -> u64 {
+        self.n
+    }
+
+    /// Number of distinct prime factors
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if there are no prime factors, which only holds for 0 and 1
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// (prime, exponent) pair at idx, in increasing order of primes
+    pub const fn get(&self, idx: usize) -> Option<(u64, u32)> {
+        if idx < self.len {
+            return Some((self.prime[idx], self.exponent[idx] as u32));
+        }
+        None
+    }
+
+    /// Check if N is prime
+    pub const fn is_prime(&self) -> bool {
+        self.len == 1 && self.exponent[0] == 1
+    }
+
+    /// Iterator over the (prime, exponent) pairs
+    pub const fn iter(&self) -> FactorIter<'_> {
+        FactorIter { factors: self, idx: 0 }
+    }
+
+    // In: Prime P, exponent E
+    // Out: Adds P^E, keeping the primes in increasing order
+    const fn insert(&mut self, p: u64, e: u8) {
+        let mut idx = self.len;
+        while idx > 0 && self.prime[idx - 1] >= p {
+            idx -= 1;
+        }
+        if idx < self.len && self.prime[idx] == p {
+            self.exponent[idx] += e;
+            return;
+        }
+        let mut j = self.len;
+        while j > idx {
+            self.prime[j] = self.prime[j - 1];
+            self.exponent[j] = self.exponent[j - 1];
+            j -= 1;
+        }
+        self.prime[idx] = p;
+        self.exponent[idx] = e;
+        self.len += 1;
+    }
+}
+
+/// Iterator over the (prime, exponent) pairs of a factorisation
+#[derive(Clone, Debug)]
+pub struct FactorIter<'a> {
+    factors: &'a Factorization,
+    idx: usize,
+}
+
+impl Iterator for FactorIter<'_> {
+    type Item = (u64, u32);
+
+    fn next(&mut self) -> Option<(u64, u32)> {
+        let pair = self.factors.get(self.idx)?;
+        self.idx += 1;
+        Some(pair)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.factors.len - self.idx;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for FactorIter<'_> {}
+
+impl core::iter::FusedIterator for FactorIter<'_> {}
+
+impl<'a> IntoIterator for &'a Factorization {
+    type Item = (u64, u32);
+    type IntoIter = FactorIter<'a>;
+
+    fn into_iter(self) -> FactorIter<'a> {
+        self.iter()
+    }
+}
+
+/// Product of prime powers e.g "2^3 * 3 * 5^2"
+impl core::fmt::Display for Factorization {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.len == 0 {
+            return write!(f, "{}", self.n);
+        }
+        for (idx, (p, e)) in self.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(" * ")?;
+            }
+            if e == 1 {
+                write!(f, "{}", p)?;
+            } else {
+                write!(f, "{}^{}", p, e)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Greatest common divisor
+pub(crate) const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    if a == 0 {
+        return b;
+    }
+    if b == 0 {
+        return a;
+    }
+    let shift = (a | b).trailing_zeros();
+    a >>= a.trailing_zeros();
+    loop {
+        b >>= b.trailing_zeros();
+        if a > b {
+            (a, b) = (b, a);
+        }
+        b -= a;
+        if b == 0 {
+            return a << shift;
+        }
+    }
+}
+
+// In: N odd composite, polynomial constant C
+// Out: Non-trivial factor of N found by Brent's variant of Pollard's rho with x^2 + C, None if the cycle closes
+//      without a factor
+const fn rho(n: u64, c: u64) -> Option<u64> {
+    const BATCH: u64 = 128;
+
+    let inv = mul_inv2(n);
+    let one = one_mont(n);
+    // Any constant works, its Montgomery form is not needed
+    let c = c % n;
+
+    let mut y = one;
+    let mut x = y;
+    let mut ys = y;
+    let mut q = one;
+    let mut g = 1;
+    let mut r = 1u64;
+
+    while g == 1 {
+        x = y;
+        let mut i = 0;
+        while i < r {
+            y = mont_sub(mont_prod(y, y, inv, n), n - c, n);
+            i += 1;
+        }
+        let mut k = 0;
+        while k < r && g == 1 {
+            ys = y;
+            let mut i = 0;
+            while i < BATCH && i < r - k {
+                y = mont_sub(mont_prod(y, y, inv, n), n - c, n);
+                q = mont_prod(q, x.abs_diff(y), inv, n);
+                i += 1;
+            }
+            g = gcd(q, n);
+            k += BATCH;
+        }
+        r <<= 1;
+    }
+
+    if g == n {
+        // The batched product collapsed, step through the last batch individually
+        loop {
+            ys = mont_sub(mont_prod(ys, ys, inv, n), n - c, n);
+            g = gcd(x.abs_diff(ys), n);
+            if g != 1 {
+                break;
+            }
+        }
+    }
+
+    if g != n {
+        return Some(g);
+    }
+    None
+}
+
+// In: N odd composite, not a perfect square, with no factor up to 727
+// Out: Non-trivial factor of N by Shanks' square forms factorisation, None if every multiplier fails
+const fn squfof(n: u64) -> Option<u64> {
+    let mut idx = 0;
+    while idx < SQUFOF_MULTIPLIERS.len() {
+        let k = SQUFOF_MULTIPLIERS[idx];
+        idx += 1;
+        // kN < 2^75, the forms have coefficients below 2*sqrt(kN) < 2^38
+        let d = k as u128 * n as u128;
+        let p0 = d.isqrt() as u64;
+        let mut q = (d - p0 as u128 * p0 as u128) as u64;
+        if q == 0 {
+            // kN is square and k is squarefree and coprime to N, so N shares a factor with sqrt(kN)
+            let g = gcd(n, p0);
+            if g != 1 && g != n {
+                return Some(g);
+            }
+            continue;
+        }
+        let bound = 6 * (2 * (d.isqrt() as u64)).isqrt();
+
+        // Forward cycle until a square form is reached on an even step
+        let mut p = p0;
+        let mut p_prev = p0;
+        let mut q_prev = 1u64;
+        let mut r = 0;
+        let mut i = 2;
+        while i < bound {
+            let b = (p0 + p) / q;
+            p = b * q - p;
+            let t = q;
+            q = q_prev.wrapping_add(b.wrapping_mul(p_prev.wrapping_sub(p)));
+            if i & 1 == 0 && (SQUARE_MOD_64 >> (q & 63)) & 1 == 1 {
+                r = q.isqrt();
+                if r * r == q {
+                    break;
+                }
+            }
+            q_prev = t;
+            p_prev = p;
+            i += 1;
+        }
+        if i >= bound {
+            continue;
+        }
+
+        // Reverse cycle from the square root of the form until P repeats
+        let b = (p0 - p) / r;
+        p = b * r + p;
+        q_prev = r;
+        q = ((d - p as u128 * p as u128) / q_prev as u128) as u64;
+        let mut j = 0;
+        loop {
+            let b = (p0 + p) / q;
+            p_prev = p;
+            p = b * q - p;
+            let t = q;
+            q = q_prev.wrapping_add(b.wrapping_mul(p_prev.wrapping_sub(p)));
+            q_prev = t;
+            j += 1;
+            if p == p_prev || j >= bound {
+                break;
+            }
+        }
+        if p != p_prev {
+            continue;
+        }
+        let g = gcd(n, q_prev);
+        if g != 1 && g != n {
+            return Some(g);
+        }
+    }
+    None
+}
+
+// In: N odd composite with no factor up to 727
+// Out: Non-trivial factor of N
+const fn split(n: u64) -> u64 {
+    let r = n.isqrt();
+    if r * r == n {
+        return r;
+    }
+    let mut c = 1;
+    loop {
+        if let Some(f) = rho(n, c) {
+            return f;
+        }
+        // The cycle closed without a factor, which is rare, try SQUFOF before changing the polynomial
+        if c == 1 {
+            if let Some(f) = squfof(n) {
+                return f;
+            }
+        }
+        c += 1;
+    }
+}
+
+// In: N, factorisation, prime P
+// Out: N with every factor of P removed and recorded
+#[cfg(not(any(feature = "lucas", feature = "ssmr")))]
+const fn remove_prime(mut n: u64, factors: &mut Factorization, p: u64) -> u64 {
+    let mut e = 0;
+    while n.is_multiple_of(p) {
+        n /= p;
+        e += 1;
+    }
+    if e > 0 {
+        factors.insert(p, e);
+    }
+    n
+}
+
+/// Prime factorisation
+///
+/// In: N
+///
+/// Out: Primes dividing N and their exponents, empty for 0 and 1
+pub const fn factor(mut n: u64) -> Factorization {
+    let mut factors = Factorization::empty(n);
+    if n < 2 {
+        return factors;
+    }
+
+    let tz = n.trailing_zeros();
+    if tz > 0 {
+        factors.insert(2, tz as u8);
+        n >>= tz;
+    }
+
+    #[cfg(any(feature = "lucas", feature = "ssmr"))]
+    {
+        let mut idx = 0;
+        while idx < 256 && n > 1 {
+            // n*p^-1 <= 2^64/p if and only if p divides n, the product is then the exact quotient
+            let mut e = 0;
+            let mut prod = n.wrapping_mul(PRIME_TABLE[idx]);
+            while prod <= PRIME_TABLE[idx + 1] {
+                n = prod;
+                e += 1;
+                prod = n.wrapping_mul(PRIME_TABLE[idx]);
+            }
+            if e > 0 {
+                // The prime is the inverse of its inverse
+                factors.insert(mul_inv2(PRIME_TABLE[idx]), e);
+            }
+            idx += 2;
+        }
+    }
+
+    #[cfg(not(any(feature = "lucas", feature = "ssmr")))]
+    {
+        let mut idx = 1;
+        while idx < SIEVING_PRIMES.len() && n > 1 {
+            n = remove_prime(n, &mut factors, SIEVING_PRIMES[idx] as u64);
+            idx += 1;
+        }
+    }
+
+    if n == 1 {
+        return factors;
+    }
+    if n < TRIAL_MAX * TRIAL_MAX {
+        factors.insert(n, 1);
+        return factors;
+    }
+
+    // Cofactors have no factor up to 727 so there are at most 6 prime factors counted with multiplicity
+    let mut stack = [0u64; 8];
+    stack[0] = n;
+    let mut top = 1;
+
+    while top > 0 {
+        top -= 1;
+        let m = stack[top];
+
+        if is_prime_wc(m) {
+            factors.insert(m, 1);
+            continue;
+        }
+        let f = split(m);
+        stack[top] = f;
+        stack[top + 1] = m / f;
+        top += 2;
+    }
+    factors
+}
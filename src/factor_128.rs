@@ -0,0 +1,611 @@
+use crate::factor::factor;
+use crate::power::is_perfect_power_128;
+use crate::wheel::next_prime;
+use crate::wide::{add_mod_128, is_prime_wc_128, mont_pow_128, mont_prod_128, mont_sqr_128, mont_sub_128, mul_inv2_128, one_mont_128, to_mont_128, two_mont_128};
+
+#[cfg(any(feature = "lucas", feature = "ssmr"))]
+use crate::primes::PRIME_TABLE_128;
+
+#[cfg(not(any(feature = "lucas", feature = "ssmr")))]
+use crate::constellation::SIEVING_PRIMES;
+
+/*
+   Factorisation of 128-bit integers
+
+   After trial division by the primes up to 727, cofactors below 2^64 are passed to factor and perfect powers are
+   reduced to their roots. The remaining composites are split by, in order
+
+   - Brent's rho with a bounded cycle length, finding factors up to about 30 bits
+   - Pollard's p-1 with B1 = 10^4 and B2 = 10^6, finding factors p where p-1 is smooth
+   - Lenstra's ECM on Montgomery curves By^2 = x^3 + Ax^2 + x with Suyama's parametrisation, which have a torsion
+     subgroup of order 12 so that the group orders are more likely to be smooth. Stage 1 multiplies the point by each
+     prime power up to B1 with the Montgomery ladder, stage 2 covers a single prime q in (B1,B2] with baby steps jP
+     and giant steps kDP, as x(kDP) = x(jP) exactly when q = kD ± j is the order of the point modulo some p | N.
+     B1 is raised from 2000 to 250000 following the usual schedule for factors of 15, 20, 25 and 30 digits.
+
+   Curves and p-1 work in 128-bit Montgomery form using projective coordinates, so no modular inverse is required.
+   Every product accumulated for a gcd is in Montgomery form, which has the same gcd with N as the integer it represents.
+
+   The product of the first 27 primes exceeds 2^128 so no 128-bit integer has more than 26 distinct prime factors.
+*/
+
+/// Maximum number of distinct prime factors of a 128-bit integer
+pub const FACTOR_CAPACITY_128: usize = 26;
+
+/// Cycle length at which rho gives way to p-1 and ECM
+const RHO_LIMIT: u64 = 1 << 14;
+
+/// p-1 bounds
+const PM1_B1: u64 = 10_000;
+const PM1_B2: u64 = 1_000_000;
+
+/// ECM stage 1 bound and number of curves at that bound, the last stage continues indefinitely
+const ECM_SCHEDULE: [(u64, u32); 4] = [(2_000, 25), (11_000, 90), (50_000, 300), (250_000, u32::MAX)];
+
+/// Ratio of the stage 2 and stage 1 bounds
+const B2_RATIO: u64 = 100;
+
+/// Giant step of stage 2
+const STAGE2_D: u64 = 210;
+
+/// Number of integers in [1,D) coprime to D
+const STAGE2_PHI: usize = 48;
+
+/// Prime factorisation of a 128-bit integer
+///
+/// Primes are stored in increasing order with their exponents. The factorisation of 0 and 1 is empty.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Factorization128 {
+    n: u128,
+    len: usize,
+    prime: [u128; FACTOR_CAPACITY_128],
+    exponent: [u8; FACTOR_CAPACITY_128],
+}
+
+impl Factorization128 {
+    const fn empty(n: u128) -> Self {
+        Self { n, len: 0, prime: [0; FACTOR_CAPACITY_128], exponent: [0; FACTOR_CAPACITY_128] }
+    }
+
+    /// The integer that was factored
+    pub const fn n(&self) -> u128 {
+        self.n
+    }
+
+    /// Number of distinct prime factors
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if there are no prime factors, which only holds for 0 and 1
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// (prime, exponent) pair at idx, in increasing order of primes
+    pub const fn get(&self, idx: usize) -> Option<(u128, u32)> {
+        if idx < self.len {
+            return Some((self.prime[idx], self.exponent[idx] as u32));
+        }
+        None
+    }
+
+    /// Check if N is prime
+    pub const fn is_prime(&self) -> bool {
+        self.len == 1 && self.exponent[0] == 1
+    }
+
+    /// Iterator over the (prime, exponent) pairs
+    pub const fn iter(&self) -> FactorIter128<'_> {
+        FactorIter128 { factors: self, idx: 0 }
+    }
+
+    // In: Prime P, exponent E
+    // Out: Adds P^E, keeping the primes in increasing order
+    const fn insert(&mut self, p: u128, e: u8) {
+        let mut idx = self.len;
+        while idx > 0 && self.prime[idx - 1] >= p {
+            idx -= 1;
+        }
+        if idx < self.len && self.prime[idx] == p {
+            self.exponent[idx] += e;
+            return;
+        }
+        let mut j = self.len;
+        while j > idx {
+            self.prime[j] = self.prime[j - 1];
+            self.exponent[j] = self.exponent[j - 1];
+            j -= 1;
+        }
+        self.prime[idx] = p;
+        self.exponent[idx] = e;
+        self.len += 1;
+    }
+}
+
+/// Iterator over the (prime, exponent) pairs of a 128-bit factorisation
+#[derive(Clone, Debug)]
+pub struct FactorIter128<'a> {
+    factors: &'a Factorization128,
+    idx: usize,
+}
+
+impl Iterator for FactorIter128<'_> {
+    type Item = (u128, u32);
+
+    fn next(&mut self) -> Option<(u128, u32)> {
+        let pair = self.factors.get(self.idx)?;
+        self.idx += 1;
+        Some(pair)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.factors.len - self.idx;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for FactorIter128<'_> {}
+
+impl core::iter::FusedIterator for FactorIter128<'_> {}
+
+impl<'a> IntoIterator for &'a Factorization128 {
+    type Item = (u128, u32);
+    type IntoIter = FactorIter128<'a>;
+
+    fn into_iter(self) -> FactorIter128<'a> {
+        self.iter()
+    }
+}
+
+/// Product of prime powers e.g "2^3 * 3 * 5^2"
+impl core::fmt::Display for Factorization128 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.len == 0 {
+            return write!(f, "{}", self.n);
+        }
+        for (idx, (p, e)) in self.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(" * ")?;
+            }
+            if e == 1 {
+                write!(f, "{}", p)?;
+            } else {
+                write!(f, "{}^{}", p, e)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// In: A, B
+// Out: gcd(A,B)
+pub(crate) const fn gcd_128(mut a: u128, mut b: u128) -> u128 {
+    if a == 0 {
+        return b;
+    }
+    if b == 0 {
+        return a;
+    }
+    let shift = (a | b).trailing_zeros();
+    a >>= a.trailing_zeros();
+    loop {
+        b >>= b.trailing_zeros();
+        if a > b {
+            (a, b) = (b, a);
+        }
+        b -= a;
+        if b == 0 {
+            return a << shift;
+        }
+    }
+}
+
+// In: G = gcd of an accumulated product with N
+// Out: G if it is a non-trivial factor
+const fn nontrivial(g: u128, n: u128) -> Option<u128> {
+    if g != 1 && g != n {
+        return Some(g);
+    }
+    None
+}
+
+// In: N odd composite, polynomial constant C
+// Out: Non-trivial factor of N by Brent's rho with x^2 + C, None if no factor is found within RHO_LIMIT iterations
+const fn rho_128(n: u128, c: u128) -> Option<u128> {
+    const BATCH: u64 = 128;
+
+    let inv = mul_inv2_128(n);
+    let one = one_mont_128(n);
+
+    let mut y = one;
+    let mut x = y;
+    let mut ys = y;
+    let mut q = one;
+    let mut g = 1;
+    let mut r = 1u64;
+
+    while g == 1 {
+        if r > RHO_LIMIT {
+            return None;
+        }
+        x = y;
+        let mut i = 0;
+        while i < r {
+            y = add_mod_128(mont_sqr_128(y, inv, n), c, n);
+            i += 1;
+        }
+        let mut k = 0;
+        while k < r && g == 1 {
+            ys = y;
+            let mut i = 0;
+            while i < BATCH && i < r - k {
+                y = add_mod_128(mont_sqr_128(y, inv, n), c, n);
+                q = mont_prod_128(q, x.abs_diff(y), inv, n);
+                i += 1;
+            }
+            g = gcd_128(q, n);
+            k += BATCH;
+        }
+        r <<= 1;
+    }
+
+    if g == n {
+        // The batched product collapsed, step through the last batch individually
+        loop {
+            ys = add_mod_128(mont_sqr_128(ys, inv, n), c, n);
+            g = gcd_128(x.abs_diff(ys), n);
+            if g != 1 {
+                break;
+            }
+        }
+    }
+    nontrivial(g, n)
+}
+
+// Out: Residues in [1,D) coprime to D
+const fn stage2_residues() -> [u64; STAGE2_PHI] {
+    let mut res = [0u64; STAGE2_PHI];
+    let mut idx = 0;
+    let mut j = 1;
+    while j < STAGE2_D {
+        if j % 2 != 0 && j % 3 != 0 && j % 5 != 0 && j % 7 != 0 {
+            res[idx] = j;
+            idx += 1;
+        }
+        j += 1;
+    }
+    res
+}
+
+const STAGE2_RESIDUES: [u64; STAGE2_PHI] = stage2_residues();
+
+// In: P, B1
+// Out: Greatest power of P not exceeding B1
+const fn prime_power(p: u64, b1: u64) -> u64 {
+    let mut q = p;
+    while q <= b1 / p {
+        q *= p;
+    }
+    q
+}
+
+// In: N odd composite
+// Out: Non-trivial factor of N by Pollard's p-1 with base 2
+const fn pm1_128(n: u128) -> Option<u128> {
+    let inv = mul_inv2_128(n);
+    let one = one_mont_128(n);
+    let mut x = two_mont_128(one, n);
+
+    // Stage 1, x = 2^E where E is the product of the prime powers up to B1
+    let mut p = 2;
+    while p <= PM1_B1 {
+        x = mont_pow_128(x, one, prime_power(p, PM1_B1) as u128, inv, n);
+        p = match next_prime(p) {
+            Some(p) => p,
+            None => break,
+        };
+    }
+    let g = gcd_128(mont_sub_128(x, one, n), n);
+    if g == n {
+        return None;
+    }
+    if g != 1 {
+        return Some(g);
+    }
+
+    // Stage 2, x^q = 1 for q = kD - j if and only if x^(kD) = x^j
+    let mut baby = [0u128; STAGE2_PHI];
+    let mut idx = 0;
+    while idx < STAGE2_PHI {
+        baby[idx] = mont_pow_128(x, one, STAGE2_RESIDUES[idx] as u128, inv, n);
+        idx += 1;
+    }
+    let step = mont_pow_128(x, one, STAGE2_D as u128, inv, n);
+    let mut k = PM1_B1 / STAGE2_D + 1;
+    let mut giant = mont_pow_128(x, one, (k * STAGE2_D) as u128, inv, n);
+    let mut acc = one;
+    while k * STAGE2_D <= PM1_B2 {
+        let mut idx = 0;
+        while idx < STAGE2_PHI {
+            acc = mont_prod_128(acc, mont_sub_128(giant, baby[idx], n), inv, n);
+            idx += 1;
+        }
+        giant = mont_prod_128(giant, step, inv, n);
+        k += 1;
+    }
+    nontrivial(gcd_128(acc, n), n)
+}
+
+// Point (X : Z) on a Montgomery curve, coordinates in Montgomery form
+#[derive(Clone, Copy)]
+struct Point {
+    x: u128,
+    z: u128,
+}
+
+// Montgomery curve with (A+2)/4 = a24 / d24, and the values needed for arithmetic modulo N
+#[derive(Clone, Copy)]
+struct Curve {
+    a24: u128,
+    d24: u128,
+    inv: u128,
+    n: u128,
+}
+
+impl Curve {
+    // In: 2P
+    const fn double(&self, p: Point) -> Point {
+        let (inv, n) = (self.inv, self.n);
+        let sum = mont_sqr_128(add_mod_128(p.x, p.z, n), inv, n);
+        let diff = mont_sqr_128(mont_sub_128(p.x, p.z, n), inv, n);
+        // 4XZ
+        let t = mont_sub_128(sum, diff, n);
+        let x = mont_prod_128(mont_prod_128(sum, diff, inv, n), self.d24, inv, n);
+        let z = mont_prod_128(t, add_mod_128(mont_prod_128(diff, self.d24, inv, n), mont_prod_128(t, self.a24, inv, n), n), inv, n);
+        Point { x, z }
+    }
+
+    // In: P, Q, P - Q
+    // Out: P + Q
+    const fn add(&self, p: Point, q: Point, diff: Point) -> Point {
+        let (inv, n) = (self.inv, self.n);
+        let u = mont_prod_128(mont_sub_128(p.x, p.z, n), add_mod_128(q.x, q.z, n), inv, n);
+        let v = mont_prod_128(add_mod_128(p.x, p.z, n), mont_sub_128(q.x, q.z, n), inv, n);
+        let x = mont_prod_128(diff.z, mont_sqr_128(add_mod_128(u, v, n), inv, n), inv, n);
+        let z = mont_prod_128(diff.x, mont_sqr_128(mont_sub_128(u, v, n), inv, n), inv, n);
+        Point { x, z }
+    }
+
+    // In: P, M >= 1
+    // Out: MP by the Montgomery ladder
+    const fn multiply(&self, p: Point, m: u64) -> Point {
+        if m == 1 {
+            return p;
+        }
+        let mut r0 = p;
+        let mut r1 = self.double(p);
+        let mut bit = 62 - m.leading_zeros() as i32;
+        while bit >= 0 {
+            if (m >> bit) & 1 == 1 {
+                r0 = self.add(r1, r0, p);
+                r1 = self.double(r1);
+            } else {
+                r1 = self.add(r1, r0, p);
+                r0 = self.double(r0);
+            }
+            bit -= 1;
+        }
+        r0
+    }
+}
+
+// In: N odd composite, Suyama parameter sigma > 5, stage 1 bound
+// Out: Non-trivial factor of N found by ECM on the curve given by sigma
+const fn ecm_curve(n: u128, sigma: u128, b1: u64) -> Option<u128> {
+    let inv = mul_inv2_128(n);
+    let one = one_mont_128(n);
+
+    // u = sigma^2 - 5, v = 4 sigma, the curve has (A+2)/4 = (v-u)^3 (3u+v) / 16u^3v and starting point (u^3 : v^3)
+    let s = to_mont_128(sigma % n, n);
+    let five = to_mont_128(5 % n, n);
+    let u = mont_sub_128(mont_sqr_128(s, inv, n), five, n);
+    let two_s = add_mod_128(s, s, n);
+    let v = add_mod_128(two_s, two_s, n);
+    let u3 = mont_prod_128(mont_sqr_128(u, inv, n), u, inv, n);
+    let v3 = mont_prod_128(mont_sqr_128(v, inv, n), v, inv, n);
+    let vu = mont_sub_128(v, u, n);
+    let vu3 = mont_prod_128(mont_sqr_128(vu, inv, n), vu, inv, n);
+    let three_u_v = add_mod_128(add_mod_128(add_mod_128(u, u, n), u, n), v, n);
+    let a24 = mont_prod_128(vu3, three_u_v, inv, n);
+    let mut d24 = mont_prod_128(u3, v, inv, n);
+    let mut idx = 0;
+    while idx < 4 {
+        d24 = add_mod_128(d24, d24, n);
+        idx += 1;
+    }
+    let g = gcd_128(d24, n);
+    if g != 1 {
+        return nontrivial(g, n);
+    }
+
+    let curve = Curve { a24, d24, inv, n };
+    let mut point = Point { x: u3, z: v3 };
+
+    // Stage 1, multiply by every prime power up to B1
+    let mut p = 2;
+    while p <= b1 {
+        point = curve.multiply(point, prime_power(p, b1));
+        p = match next_prime(p) {
+            Some(p) => p,
+            None => break,
+        };
+    }
+    let g = gcd_128(point.z, n);
+    if g == n {
+        return None;
+    }
+    if g != 1 {
+        return Some(g);
+    }
+
+    // Stage 2, baby steps jP for odd j < D/2 coprime to D and giant steps kDP
+    let mut baby = [Point { x: 0, z: 0 }; STAGE2_PHI / 2];
+    let p2 = curve.double(point);
+    let mut prev = point;
+    let mut current = curve.add(p2, point, point);
+    baby[0] = point;
+    let mut count = 1;
+    let mut j = 3;
+    while j < STAGE2_D / 2 {
+        if j % 3 != 0 && j % 5 != 0 && j % 7 != 0 {
+            baby[count] = current;
+            count += 1;
+        }
+        let next = curve.add(current, p2, prev);
+        prev = current;
+        current = next;
+        j += 2;
+    }
+
+    let b2 = b1 * B2_RATIO;
+    let step = curve.multiply(point, STAGE2_D);
+    let mut k = b1 / STAGE2_D + 1;
+    let mut giant_prev = curve.multiply(point, (k - 1) * STAGE2_D);
+    let mut giant = curve.multiply(point, k * STAGE2_D);
+    let mut acc = one;
+    while (k - 1) * STAGE2_D <= b2 {
+        let mut idx = 0;
+        while idx < STAGE2_PHI / 2 {
+            let b = baby[idx];
+            let cross = mont_sub_128(mont_prod_128(giant.x, b.z, inv, n), mont_prod_128(b.x, giant.z, inv, n), n);
+            acc = mont_prod_128(acc, cross, inv, n);
+            idx += 1;
+        }
+        let next = curve.add(giant, step, giant_prev);
+        giant_prev = giant;
+        giant = next;
+        k += 1;
+    }
+    nontrivial(gcd_128(acc, n), n)
+}
+
+// In: N odd composite, not a perfect power, with no factor up to 727
+// Out: Non-trivial factor of N
+const fn split_128(n: u128) -> u128 {
+    if let Some(f) = rho_128(n, 1) {
+        return f;
+    }
+    if let Some(f) = pm1_128(n) {
+        return f;
+    }
+    let mut sigma = 6;
+    let mut stage = 0;
+    loop {
+        let (b1, curves) = ECM_SCHEDULE[stage];
+        let mut idx = 0;
+        while idx < curves {
+            if let Some(f) = ecm_curve(n, sigma, b1) {
+                return f;
+            }
+            sigma += 1;
+            idx += 1;
+        }
+        stage += 1;
+    }
+}
+
+// In: N, factorisation, prime P
+// Out: N with every factor of P removed and recorded
+#[cfg(not(any(feature = "lucas", feature = "ssmr")))]
+const fn remove_prime(mut n: u128, factors: &mut Factorization128, p: u128) -> u128 {
+    let mut e = 0;
+    while n.is_multiple_of(p) {
+        n /= p;
+        e += 1;
+    }
+    if e > 0 {
+        factors.insert(p, e);
+    }
+    n
+}
+
+/// Prime factorisation, 128-bit form
+///
+/// In: N
+///
+/// Out: Primes dividing N and their exponents, empty for 0 and 1
+pub const fn factor_128(mut n: u128) -> Factorization128 {
+    let mut factors = Factorization128::empty(n);
+    if n < 2 {
+        return factors;
+    }
+
+    let tz = n.trailing_zeros();
+    if tz > 0 {
+        factors.insert(2, tz as u8);
+        n >>= tz;
+    }
+
+    #[cfg(any(feature = "lucas", feature = "ssmr"))]
+    {
+        let mut idx = 0;
+        while idx < 256 && n > 1 {
+            let mut e = 0;
+            let mut prod = n.wrapping_mul(PRIME_TABLE_128[idx]);
+            while prod <= PRIME_TABLE_128[idx + 1] {
+                n = prod;
+                e += 1;
+                prod = n.wrapping_mul(PRIME_TABLE_128[idx]);
+            }
+            if e > 0 {
+                factors.insert(mul_inv2_128(PRIME_TABLE_128[idx]), e);
+            }
+            idx += 2;
+        }
+    }
+
+    #[cfg(not(any(feature = "lucas", feature = "ssmr")))]
+    {
+        let mut idx = 1;
+        while idx < SIEVING_PRIMES.len() && n > 1 {
+            n = remove_prime(n, &mut factors, SIEVING_PRIMES[idx] as u128);
+            idx += 1;
+        }
+    }
+
+    // Cofactors have no factor up to 727 so there are at most 13 prime factors counted with multiplicity. Each is
+    // stored with the exponent it carries from perfect powers
+    let mut stack = [(0u128, 0u8); 16];
+    stack[0] = (n, 1);
+    let mut top = if n > 1 { 1 } else { 0 };
+
+    while top > 0 {
+        top -= 1;
+        let (m, e) = stack[top];
+
+        if m <= u64::MAX as u128 {
+            let small = factor(m as u64);
+            let mut idx = 0;
+            while let Some((p, pe)) = small.get(idx) {
+                factors.insert(p as u128, pe as u8 * e);
+                idx += 1;
+            }
+            continue;
+        }
+        if is_prime_wc_128(m) {
+            factors.insert(m, e);
+            continue;
+        }
+        if let Some((root, k)) = is_perfect_power_128(m) {
+            stack[top] = (root, e * k as u8);
+            top += 1;
+            continue;
+        }
+        let f = split_128(m);
+        stack[top] = (f, e);
+        stack[top + 1] = (m / f, e);
+        top += 2;
+    }
+    factors
+}
@@ -16392,3 +16392,269 @@ pub static FERMAT_WITNESS : [u16;262144] = [
 	 589, 439, 364, 23, 498, 152, 1727, 6612, 59, 190, 3241, 156, 189, 458, 2313, 2440,
 	 1599, 42, 518, 1195, 28, 333, 359, 234, 489, 1055, 47, 944, 551, 95, 1111, 3056,
 	 ];
+
+#[rustfmt::skip]
+#[cfg(feature="ssmr")]
+/// Table of Fermat witnesses for odd n < 2^32
+#[no_mangle]
+pub static FERMAT_WITNESS_32 : [u8;4096] = [
+//divisor: 1048576 multiplier: 2654435761
+// hash(x)  = x.wrapping_mul(2654435761)/1048576
+    // Each witness is a strong fermat witness for every odd composite in its bucket and coprime to every prime in it
+	 3, 5, 5, 2, 3, 6, 2, 3, 3, 3, 7, 2, 3, 5, 2, 2,
+	 3, 2, 2, 2, 3, 2, 2, 6, 2, 2, 2, 3, 2, 6, 5, 3,
+	 2, 3, 6, 3, 10, 2, 5, 2, 2, 5, 2, 2, 2, 2, 2, 2,
+	 2, 3, 2, 2, 5, 3, 2, 2, 2, 2, 3, 3, 7, 2, 2, 2,
+	 5, 2, 3, 2, 7, 2, 3, 3, 7, 2, 5, 3, 5, 2, 5, 2,
+	 2, 5, 2, 6, 2, 2, 3, 5, 2, 3, 3, 2, 6, 2, 3, 2,
+	 2, 5, 5, 2, 3, 5, 2, 2, 2, 6, 6, 2, 2, 2, 2, 3,
+	 2, 2, 2, 5, 2, 5, 3, 5, 2, 2, 5, 5, 5, 2, 2, 2,
+	 2, 6, 2, 6, 5, 2, 2, 2, 5, 5, 2, 2, 3, 3, 2, 5,
+	 3, 2, 2, 2, 5, 3, 3, 5, 2, 3, 2, 3, 2, 2, 2, 2,
+	 3, 2, 2, 3, 2, 6, 3, 6, 3, 2, 5, 3, 2, 2, 3, 2,
+	 2, 3, 3, 2, 2, 6, 5, 3, 3, 11, 2, 2, 6, 2, 2, 3,
+	 12, 3, 2, 3, 2, 3, 2, 2, 2, 6, 3, 6, 2, 3, 2, 2,
+	 11, 2, 2, 2, 2, 7, 2, 3, 2, 5, 2, 2, 2, 5, 2, 2,
+	 5, 2, 2, 3, 5, 2, 6, 2, 7, 3, 3, 5, 2, 3, 2, 2,
+	 2, 5, 3, 2, 7, 5, 3, 5, 2, 3, 5, 2, 2, 2, 2, 2,
+	 2, 2, 2, 2, 6, 2, 5, 2, 2, 2, 2, 3, 2, 2, 2, 5,
+	 2, 2, 2, 2, 2, 3, 2, 5, 2, 6, 5, 5, 2, 2, 7, 5,
+	 6, 2, 5, 2, 3, 2, 2, 7, 2, 5, 2, 2, 3, 2, 3, 2,
+	 7, 3, 7, 3, 2, 2, 3, 3, 2, 5, 5, 2, 3, 2, 5, 2,
+	 2, 3, 3, 2, 2, 2, 2, 3, 2, 3, 6, 2, 3, 2, 2, 2,
+	 3, 5, 2, 6, 2, 2, 2, 3, 2, 2, 6, 2, 2, 2, 3, 3,
+	 5, 3, 2, 2, 2, 2, 2, 5, 3, 6, 2, 2, 2, 2, 2, 2,
+	 2, 2, 2, 2, 2, 2, 5, 6, 5, 5, 2, 3, 2, 2, 3, 2,
+	 2, 2, 2, 5, 3, 2, 2, 3, 3, 2, 2, 2, 3, 6, 2, 2,
+	 6, 2, 5, 3, 5, 5, 2, 2, 7, 3, 2, 2, 3, 2, 2, 2,
+	 3, 10, 2, 3, 3, 2, 2, 2, 5, 2, 2, 5, 5, 5, 2, 2,
+	 2, 6, 2, 2, 6, 2, 2, 2, 3, 11, 3, 2, 3, 6, 2, 3,
+	 3, 2, 2, 2, 5, 3, 3, 2, 6, 2, 2, 5, 3, 2, 5, 7,
+	 2, 3, 2, 3, 3, 5, 2, 3, 2, 3, 5, 7, 6, 3, 2, 2,
+	 2, 3, 2, 2, 7, 2, 2, 2, 2, 2, 7, 2, 2, 5, 2, 2,
+	 6, 2, 5, 2, 3, 2, 5, 3, 5, 2, 2, 2, 2, 2, 5, 2,
+	 12, 2, 2, 2, 2, 2, 2, 3, 3, 3, 6, 2, 2, 2, 2, 2,
+	 2, 2, 2, 5, 3, 2, 3, 2, 2, 6, 2, 2, 2, 3, 2, 2,
+	 2, 7, 2, 2, 2, 2, 2, 2, 2, 2, 3, 2, 2, 2, 2, 3,
+	 3, 6, 2, 6, 5, 2, 2, 5, 3, 2, 3, 2, 2, 5, 2, 2,
+	 5, 2, 2, 2, 5, 5, 2, 2, 10, 5, 5, 10, 6, 3, 3, 3,
+	 2, 2, 2, 5, 11, 2, 5, 5, 2, 2, 3, 5, 2, 2, 2, 2,
+	 6, 3, 2, 2, 5, 2, 2, 3, 2, 5, 2, 2, 3, 2, 3, 2,
+	 3, 2, 2, 2, 3, 2, 2, 2, 2, 2, 5, 3, 3, 2, 2, 2,
+	 2, 3, 3, 3, 7, 2, 3, 6, 3, 5, 3, 2, 2, 2, 3, 2,
+	 3, 2, 3, 3, 2, 2, 2, 2, 5, 2, 5, 2, 5, 2, 5, 6,
+	 2, 11, 2, 2, 5, 2, 2, 2, 2, 2, 2, 2, 2, 7, 2, 3,
+	 2, 2, 2, 3, 2, 2, 3, 2, 3, 2, 6, 2, 2, 2, 2, 3,
+	 3, 3, 5, 3, 3, 2, 2, 5, 2, 2, 2, 3, 6, 5, 3, 7,
+	 3, 5, 3, 3, 2, 3, 5, 2, 2, 2, 3, 2, 6, 2, 2, 2,
+	 6, 6, 3, 2, 5, 2, 2, 3, 2, 10, 12, 2, 2, 3, 5, 2,
+	 2, 2, 5, 2, 2, 3, 2, 10, 3, 2, 2, 2, 5, 2, 2, 2,
+	 5, 6, 2, 10, 3, 3, 3, 3, 5, 7, 3, 5, 2, 2, 2, 7,
+	 2, 2, 2, 2, 2, 3, 2, 5, 7, 2, 2, 3, 5, 2, 3, 2,
+	 6, 10, 2, 2, 2, 3, 3, 10, 2, 3, 2, 3, 2, 3, 11, 2,
+	 2, 2, 2, 2, 2, 2, 2, 2, 2, 7, 6, 5, 3, 5, 2, 2,
+	 2, 2, 2, 2, 2, 3, 5, 2, 3, 2, 2, 3, 2, 3, 3, 5,
+	 2, 5, 2, 5, 3, 3, 2, 5, 2, 3, 2, 7, 2, 2, 2, 2,
+	 3, 10, 3, 2, 2, 2, 2, 6, 2, 2, 2, 2, 2, 2, 5, 5,
+	 2, 2, 2, 2, 7, 2, 2, 2, 3, 3, 2, 2, 5, 5, 3, 2,
+	 2, 2, 2, 3, 2, 2, 2, 2, 2, 2, 2, 3, 2, 2, 2, 2,
+	 2, 2, 2, 2, 2, 3, 2, 2, 2, 2, 2, 2, 2, 3, 10, 2,
+	 2, 2, 2, 5, 3, 2, 2, 3, 2, 5, 3, 5, 2, 5, 2, 2,
+	 2, 2, 2, 2, 2, 6, 2, 2, 6, 3, 2, 2, 5, 2, 3, 2,
+	 7, 5, 2, 2, 3, 2, 6, 5, 3, 3, 5, 2, 3, 2, 2, 2,
+	 3, 5, 2, 3, 2, 2, 2, 7, 2, 3, 2, 2, 6, 2, 2, 3,
+	 5, 7, 2, 5, 2, 3, 2, 2, 2, 3, 2, 7, 2, 2, 5, 3,
+	 2, 2, 5, 2, 3, 2, 3, 2, 2, 2, 3, 2, 3, 3, 2, 2,
+	 2, 2, 2, 2, 2, 3, 2, 2, 10, 2, 3, 2, 2, 5, 2, 10,
+	 2, 2, 2, 2, 2, 5, 2, 2, 5, 2, 3, 2, 2, 2, 2, 2,
+	 3, 2, 2, 2, 2, 5, 3, 5, 2, 5, 3, 10, 2, 2, 5, 6,
+	 2, 2, 6, 2, 3, 10, 2, 2, 5, 2, 3, 2, 10, 3, 7, 5,
+	 2, 5, 2, 3, 2, 2, 2, 2, 2, 2, 3, 2, 2, 5, 2, 2,
+	 11, 2, 3, 2, 5, 2, 5, 3, 2, 2, 2, 2, 2, 2, 5, 2,
+	 7, 2, 5, 3, 2, 2, 6, 2, 2, 2, 3, 3, 5, 2, 2, 2,
+	 3, 2, 5, 6, 7, 2, 3, 2, 2, 2, 2, 2, 2, 3, 3, 2,
+	 2, 2, 3, 3, 2, 3, 5, 2, 10, 6, 2, 5, 2, 2, 2, 5,
+	 2, 2, 2, 3, 2, 2, 2, 3, 3, 3, 3, 2, 2, 6, 6, 2,
+	 2, 2, 2, 2, 2, 2, 3, 3, 2, 2, 2, 3, 6, 3, 3, 3,
+	 2, 2, 3, 2, 10, 6, 2, 2, 3, 2, 2, 2, 2, 2, 3, 5,
+	 2, 2, 6, 20, 2, 2, 2, 3, 5, 5, 2, 2, 2, 3, 5, 2,
+	 2, 2, 3, 2, 3, 3, 2, 2, 2, 2, 2, 2, 3, 13, 3, 3,
+	 2, 5, 2, 5, 3, 2, 2, 5, 12, 2, 2, 2, 3, 3, 7, 6,
+	 3, 2, 2, 2, 7, 2, 5, 2, 2, 2, 2, 3, 2, 3, 2, 2,
+	 2, 2, 2, 3, 2, 2, 2, 3, 2, 2, 2, 6, 3, 3, 2, 2,
+	 5, 2, 2, 5, 2, 5, 2, 2, 2, 3, 3, 2, 5, 2, 2, 2,
+	 2, 2, 5, 10, 2, 2, 2, 6, 2, 2, 3, 3, 2, 3, 2, 7,
+	 2, 2, 7, 2, 2, 7, 3, 2, 3, 3, 2, 5, 2, 2, 6, 5,
+	 2, 5, 2, 2, 2, 2, 2, 2, 5, 2, 5, 6, 2, 2, 5, 2,
+	 2, 5, 2, 2, 2, 3, 6, 3, 5, 2, 2, 2, 2, 2, 5, 5,
+	 7, 5, 6, 3, 3, 2, 2, 3, 3, 2, 2, 2, 6, 2, 2, 2,
+	 2, 11, 3, 2, 2, 2, 2, 2, 3, 5, 3, 5, 2, 2, 6, 2,
+	 2, 2, 5, 2, 2, 2, 2, 2, 10, 2, 2, 2, 2, 2, 5, 2,
+	 2, 3, 5, 6, 3, 2, 6, 2, 2, 6, 6, 2, 2, 10, 5, 3,
+	 2, 2, 6, 2, 5, 5, 3, 2, 2, 2, 2, 10, 3, 2, 2, 2,
+	 2, 2, 3, 2, 3, 5, 2, 2, 6, 2, 3, 2, 2, 2, 2, 2,
+	 2, 2, 3, 3, 2, 2, 3, 2, 2, 5, 5, 2, 3, 2, 7, 3,
+	 2, 3, 5, 2, 2, 2, 2, 2, 5, 7, 11, 6, 5, 2, 7, 11,
+	 5, 2, 3, 7, 2, 7, 5, 2, 2, 7, 2, 5, 5, 10, 2, 7,
+	 2, 2, 3, 2, 11, 3, 2, 3, 2, 5, 6, 6, 6, 2, 3, 3,
+	 2, 2, 2, 5, 2, 10, 2, 2, 3, 2, 2, 3, 2, 2, 5, 5,
+	 6, 2, 2, 2, 5, 7, 3, 6, 5, 3, 3, 6, 6, 6, 3, 2,
+	 2, 3, 2, 3, 2, 3, 2, 3, 3, 3, 2, 3, 2, 3, 2, 2,
+	 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 3, 5, 5, 2, 3,
+	 2, 2, 2, 3, 2, 5, 2, 7, 2, 2, 2, 3, 2, 2, 6, 5,
+	 2, 5, 2, 5, 3, 2, 2, 2, 2, 2, 2, 3, 2, 2, 2, 2,
+	 15, 2, 2, 5, 7, 6, 2, 2, 2, 10, 5, 3, 2, 5, 3, 5,
+	 2, 5, 2, 2, 2, 5, 3, 2, 2, 3, 2, 3, 2, 2, 2, 3,
+	 2, 6, 2, 3, 2, 2, 3, 2, 3, 2, 6, 2, 3, 2, 2, 2,
+	 5, 3, 2, 3, 5, 2, 6, 2, 2, 2, 2, 2, 5, 6, 6, 3,
+	 2, 2, 6, 2, 3, 2, 2, 2, 2, 2, 2, 5, 3, 3, 2, 2,
+	 3, 2, 3, 2, 2, 3, 2, 6, 7, 2, 3, 2, 2, 2, 6, 2,
+	 2, 3, 3, 2, 2, 3, 2, 6, 5, 2, 2, 3, 2, 2, 6, 2,
+	 2, 3, 5, 5, 3, 2, 2, 3, 2, 2, 2, 2, 2, 2, 2, 5,
+	 5, 2, 2, 2, 5, 3, 2, 3, 3, 2, 3, 2, 3, 3, 2, 3,
+	 5, 2, 2, 2, 2, 5, 2, 2, 5, 5, 2, 2, 7, 2, 2, 2,
+	 2, 2, 2, 2, 3, 5, 6, 6, 2, 2, 3, 2, 2, 3, 2, 2,
+	 5, 2, 2, 5, 2, 5, 7, 2, 2, 3, 2, 2, 2, 3, 2, 2,
+	 2, 2, 2, 2, 2, 2, 2, 3, 5, 2, 2, 2, 3, 5, 2, 2,
+	 2, 2, 3, 2, 7, 2, 2, 11, 3, 3, 2, 2, 2, 6, 2, 3,
+	 3, 2, 2, 2, 2, 2, 2, 6, 2, 2, 3, 2, 5, 2, 3, 2,
+	 7, 2, 2, 5, 2, 5, 2, 3, 2, 2, 2, 3, 3, 3, 3, 6,
+	 2, 2, 7, 3, 2, 2, 2, 2, 2, 5, 2, 3, 5, 2, 3, 2,
+	 3, 5, 2, 5, 3, 2, 6, 2, 5, 2, 3, 2, 2, 3, 2, 5,
+	 5, 11, 3, 5, 3, 2, 2, 3, 2, 2, 6, 2, 3, 5, 2, 3,
+	 2, 2, 2, 3, 2, 3, 3, 3, 2, 3, 2, 5, 3, 3, 2, 2,
+	 2, 2, 2, 2, 2, 2, 3, 2, 2, 3, 5, 2, 2, 11, 3, 2,
+	 2, 2, 12, 3, 2, 2, 2, 3, 2, 5, 2, 2, 2, 3, 2, 2,
+	 2, 2, 2, 2, 5, 2, 2, 3, 2, 2, 3, 2, 2, 2, 2, 2,
+	 2, 2, 5, 5, 5, 5, 2, 5, 6, 3, 3, 5, 2, 2, 3, 2,
+	 3, 2, 2, 5, 2, 2, 2, 2, 2, 3, 2, 5, 6, 6, 2, 5,
+	 2, 7, 2, 3, 3, 3, 7, 3, 2, 6, 2, 3, 2, 2, 2, 2,
+	 2, 3, 2, 2, 3, 2, 2, 2, 2, 2, 3, 2, 2, 2, 2, 5,
+	 6, 2, 3, 5, 3, 3, 2, 2, 2, 2, 2, 2, 6, 3, 2, 5,
+	 3, 5, 5, 3, 2, 2, 2, 3, 2, 3, 5, 5, 5, 2, 5, 2,
+	 2, 2, 2, 5, 5, 3, 3, 5, 2, 3, 2, 3, 11, 2, 6, 2,
+	 3, 2, 2, 3, 6, 2, 3, 2, 2, 2, 5, 2, 6, 6, 3, 2,
+	 2, 2, 2, 2, 2, 2, 10, 2, 5, 2, 3, 2, 2, 3, 2, 3,
+	 2, 2, 3, 2, 2, 5, 2, 2, 3, 3, 7, 2, 2, 3, 5, 7,
+	 3, 2, 2, 5, 2, 2, 3, 6, 2, 2, 2, 3, 2, 2, 2, 6,
+	 7, 6, 3, 2, 3, 2, 2, 2, 2, 2, 6, 6, 2, 7, 2, 7,
+	 2, 2, 3, 2, 3, 3, 3, 2, 2, 5, 2, 2, 5, 2, 7, 7,
+	 3, 2, 2, 5, 2, 2, 2, 2, 10, 2, 5, 2, 5, 2, 12, 5,
+	 7, 6, 2, 2, 2, 3, 5, 3, 2, 2, 2, 5, 2, 3, 2, 2,
+	 2, 2, 2, 2, 2, 3, 3, 2, 2, 3, 3, 2, 6, 2, 2, 3,
+	 2, 2, 6, 2, 5, 6, 3, 2, 2, 2, 2, 2, 6, 2, 2, 5,
+	 3, 2, 2, 2, 5, 2, 2, 2, 2, 2, 6, 2, 2, 2, 2, 2,
+	 2, 2, 2, 2, 5, 2, 3, 2, 2, 2, 2, 2, 3, 2, 2, 3,
+	 2, 5, 2, 2, 3, 2, 3, 2, 2, 2, 2, 5, 3, 2, 2, 2,
+	 2, 3, 3, 2, 2, 2, 2, 2, 5, 2, 2, 2, 2, 6, 3, 3,
+	 3, 3, 2, 6, 2, 2, 2, 3, 6, 2, 7, 2, 2, 5, 3, 3,
+	 2, 5, 10, 2, 2, 2, 5, 2, 2, 2, 2, 10, 7, 2, 3, 2,
+	 10, 2, 5, 7, 2, 2, 7, 2, 2, 2, 2, 2, 2, 2, 10, 2,
+	 6, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 2, 6, 2, 3, 2,
+	 2, 2, 2, 7, 2, 3, 6, 3, 2, 2, 2, 3, 5, 10, 3, 2,
+	 2, 2, 2, 3, 3, 5, 2, 3, 2, 3, 6, 6, 2, 3, 2, 2,
+	 2, 2, 2, 2, 6, 5, 2, 2, 2, 2, 3, 3, 3, 3, 3, 6,
+	 6, 6, 2, 3, 2, 2, 2, 2, 5, 3, 3, 2, 3, 3, 2, 5,
+	 2, 2, 2, 5, 2, 2, 2, 2, 2, 2, 2, 2, 5, 2, 7, 6,
+	 2, 2, 3, 2, 2, 2, 2, 2, 2, 5, 2, 3, 2, 2, 2, 3,
+	 3, 2, 2, 2, 3, 6, 2, 6, 5, 2, 2, 2, 5, 2, 2, 3,
+	 2, 2, 2, 3, 2, 5, 2, 2, 3, 2, 2, 5, 3, 5, 2, 6,
+	 2, 2, 3, 2, 2, 3, 6, 2, 2, 6, 2, 2, 5, 2, 3, 2,
+	 2, 3, 2, 2, 3, 3, 5, 2, 2, 2, 2, 2, 3, 2, 2, 2,
+	 3, 6, 5, 3, 3, 3, 3, 2, 2, 2, 2, 2, 2, 5, 10, 2,
+	 5, 2, 2, 2, 10, 2, 11, 2, 2, 3, 2, 2, 5, 2, 2, 2,
+	 2, 5, 3, 3, 2, 5, 2, 5, 3, 2, 10, 3, 2, 6, 2, 2,
+	 5, 3, 2, 3, 2, 2, 3, 3, 3, 2, 2, 2, 3, 5, 6, 2,
+	 2, 2, 2, 2, 2, 2, 2, 5, 3, 3, 5, 2, 5, 2, 5, 2,
+	 2, 2, 2, 2, 2, 3, 2, 2, 7, 2, 2, 2, 2, 6, 3, 2,
+	 3, 7, 6, 2, 2, 2, 2, 2, 2, 5, 2, 2, 2, 2, 2, 2,
+	 3, 3, 3, 2, 11, 5, 3, 2, 2, 3, 2, 2, 5, 5, 5, 2,
+	 6, 2, 2, 2, 2, 3, 5, 2, 7, 2, 3, 3, 2, 2, 2, 5,
+	 2, 2, 3, 2, 7, 2, 5, 3, 5, 2, 5, 2, 2, 10, 2, 2,
+	 2, 3, 2, 6, 2, 2, 5, 2, 2, 2, 2, 2, 2, 17, 5, 2,
+	 7, 2, 3, 2, 6, 3, 3, 3, 2, 2, 2, 2, 2, 2, 2, 3,
+	 2, 3, 2, 3, 5, 2, 3, 5, 2, 5, 3, 3, 2, 6, 3, 2,
+	 3, 5, 3, 3, 2, 2, 2, 2, 3, 2, 5, 3, 2, 2, 2, 2,
+	 5, 5, 5, 11, 2, 2, 2, 2, 2, 5, 3, 5, 2, 2, 7, 6,
+	 3, 2, 3, 3, 3, 2, 2, 3, 2, 6, 2, 2, 3, 2, 7, 2,
+	 2, 5, 2, 2, 5, 5, 2, 2, 2, 2, 2, 2, 2, 2, 5, 2,
+	 2, 2, 2, 3, 2, 2, 2, 3, 2, 2, 5, 2, 2, 2, 3, 2,
+	 5, 3, 5, 2, 3, 2, 7, 2, 2, 3, 2, 3, 2, 2, 5, 3,
+	 2, 3, 6, 2, 2, 10, 3, 2, 2, 3, 2, 2, 2, 2, 2, 2,
+	 2, 2, 2, 2, 3, 3, 3, 3, 2, 6, 2, 6, 2, 7, 2, 3,
+	 2, 2, 3, 2, 2, 2, 12, 2, 2, 3, 2, 2, 2, 2, 2, 2,
+	 2, 2, 2, 2, 2, 6, 2, 2, 2, 10, 3, 2, 5, 3, 2, 5,
+	 2, 2, 11, 5, 2, 2, 2, 2, 3, 5, 7, 2, 2, 5, 2, 2,
+	 2, 3, 3, 2, 6, 2, 2, 11, 2, 2, 3, 2, 3, 2, 6, 3,
+	 2, 2, 3, 2, 5, 2, 3, 2, 2, 3, 7, 2, 3, 2, 2, 2,
+	 3, 7, 5, 3, 5, 2, 2, 2, 3, 2, 3, 7, 2, 2, 2, 3,
+	 6, 2, 3, 2, 3, 2, 2, 2, 5, 2, 2, 3, 3, 3, 2, 2,
+	 6, 2, 2, 10, 5, 6, 2, 3, 2, 2, 5, 2, 2, 6, 3, 2,
+	 2, 5, 2, 2, 2, 2, 2, 5, 6, 2, 2, 2, 2, 5, 2, 2,
+	 5, 3, 3, 3, 2, 2, 2, 6, 6, 3, 2, 3, 3, 2, 3, 2,
+	 3, 3, 2, 3, 5, 2, 2, 2, 3, 2, 3, 3, 3, 2, 3, 2,
+	 2, 2, 2, 3, 5, 2, 2, 2, 2, 5, 6, 2, 2, 2, 3, 6,
+	 6, 2, 2, 2, 3, 5, 3, 6, 2, 2, 3, 5, 5, 2, 3, 2,
+	 3, 5, 3, 2, 2, 2, 2, 3, 3, 2, 5, 2, 7, 2, 7, 11,
+	 5, 2, 6, 2, 3, 2, 5, 3, 5, 2, 6, 3, 3, 2, 10, 2,
+	 2, 6, 3, 2, 2, 2, 6, 2, 3, 7, 2, 2, 2, 3, 3, 5,
+	 2, 6, 2, 5, 5, 2, 2, 3, 3, 2, 3, 5, 2, 3, 2, 3,
+	 10, 3, 2, 2, 3, 2, 2, 2, 2, 2, 2, 2, 6, 2, 3, 2,
+	 2, 2, 2, 3, 2, 7, 2, 2, 2, 2, 2, 10, 3, 2, 7, 2,
+	 2, 3, 3, 10, 2, 3, 2, 2, 2, 2, 2, 2, 3, 2, 2, 5,
+	 3, 2, 2, 2, 5, 2, 2, 2, 2, 5, 2, 2, 6, 2, 6, 2,
+	 2, 2, 5, 5, 2, 7, 2, 3, 2, 2, 2, 2, 5, 5, 2, 2,
+	 6, 2, 2, 2, 2, 6, 2, 3, 6, 2, 2, 2, 3, 3, 5, 11,
+	 2, 2, 2, 12, 2, 5, 2, 2, 13, 3, 3, 6, 2, 2, 2, 3,
+	 2, 3, 5, 3, 2, 5, 3, 2, 2, 2, 2, 3, 3, 2, 3, 2,
+	 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 2, 3, 2, 3, 2,
+	 6, 2, 2, 6, 5, 7, 3, 2, 3, 2, 3, 2, 2, 2, 3, 2,
+	 2, 2, 2, 2, 2, 3, 7, 2, 2, 3, 2, 2, 5, 5, 2, 5,
+	 3, 3, 3, 2, 3, 2, 2, 3, 2, 2, 2, 3, 5, 2, 5, 7,
+	 6, 3, 2, 2, 5, 2, 6, 6, 3, 5, 2, 2, 5, 2, 2, 2,
+	 5, 2, 2, 3, 2, 2, 2, 5, 5, 5, 2, 3, 2, 2, 2, 2,
+	 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 6, 2, 3, 3, 2, 5,
+	 3, 3, 6, 2, 2, 2, 3, 2, 11, 3, 3, 2, 2, 2, 2, 2,
+	 5, 2, 2, 3, 3, 5, 3, 3, 5, 5, 2, 2, 2, 2, 5, 12,
+	 2, 2, 3, 2, 10, 2, 5, 2, 3, 3, 3, 2, 2, 2, 2, 5,
+	 2, 2, 5, 5, 2, 3, 3, 2, 2, 2, 2, 5, 3, 2, 5, 3,
+	 2, 3, 2, 6, 2, 2, 3, 2, 2, 3, 2, 2, 2, 2, 2, 5,
+	 7, 2, 5, 5, 5, 2, 2, 2, 11, 3, 5, 3, 3, 2, 5, 2,
+	 7, 2, 3, 2, 2, 3, 7, 2, 15, 5, 5, 2, 3, 5, 2, 3,
+	 2, 7, 2, 7, 5, 3, 5, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+	 5, 3, 3, 2, 2, 2, 5, 3, 2, 2, 3, 2, 5, 2, 2, 2,
+	 3, 2, 2, 3, 2, 2, 6, 5, 5, 2, 3, 2, 2, 2, 2, 2,
+	 2, 2, 2, 2, 2, 6, 2, 2, 3, 5, 2, 2, 2, 2, 3, 6,
+	 3, 2, 3, 2, 3, 2, 3, 3, 5, 2, 2, 2, 2, 2, 2, 6,
+	 10, 14, 3, 2, 3, 2, 5, 2, 5, 2, 2, 5, 3, 5, 2, 2,
+	 2, 2, 2, 2, 2, 2, 2, 2, 2, 7, 2, 2, 7, 2, 6, 2,
+	 6, 5, 2, 2, 3, 2, 2, 2, 3, 3, 2, 2, 2, 2, 5, 2,
+	 3, 3, 2, 6, 2, 3, 2, 2, 2, 2, 2, 2, 5, 2, 5, 2,
+	 2, 2, 2, 2, 6, 10, 2, 5, 3, 2, 3, 3, 2, 2, 7, 2,
+	 2, 2, 2, 3, 2, 2, 2, 3, 3, 3, 3, 2, 3, 2, 2, 5,
+	 2, 3, 6, 2, 3, 2, 2, 6, 6, 2, 3, 2, 2, 2, 2, 2,
+	 2, 3, 2, 2, 5, 3, 2, 3, 2, 2, 2, 2, 10, 3, 3, 3,
+	 2, 2, 3, 5, 2, 6, 2, 2, 2, 2, 2, 2, 2, 2, 7, 3,
+	 3, 2, 2, 5, 2, 2, 2, 2, 2, 3, 3, 6, 5, 3, 2, 3,
+	 2, 5, 5, 2, 5, 3, 2, 2, 2, 3, 2, 2, 2, 2, 2, 2,
+	 3, 5, 5, 5, 3, 2, 2, 3, 3, 2, 3, 2, 2, 2, 2, 2,
+	 2, 2, 2, 3, 2, 10, 3, 5, 3, 2, 2, 2, 5, 2, 3, 5,
+	 2, 2, 5, 3, 5, 6, 2, 2, 2, 2, 5, 2, 7, 2, 5, 3,
+	 2, 2, 2, 2, 3, 2, 5, 2, 2, 2, 2, 2, 2, 7, 2, 2,
+	 6, 2, 2, 5, 3, 2, 5, 3, 2, 2, 3, 2, 5, 3, 2, 2,
+	 3, 2, 2, 2, 3, 2, 3, 2, 2, 2, 2, 2, 2, 2, 5, 2,
+	 2, 5, 2, 2, 2, 2, 3, 2, 3, 2, 2, 2, 3, 6, 2, 2,
+	 2, 5, 3, 3, 2, 7, 2, 2, 2, 3, 2, 7, 2, 5, 2, 6,
+	 2, 3, 2, 2, 5, 2, 3, 5, 2, 5, 2, 2, 2, 6, 6, 2,
+	 5, 2, 2, 7, 2, 3, 2, 2, 2, 2, 2, 5, 5, 2, 5, 7,
+	 3, 2, 2, 2, 5, 2, 5, 5, 3, 5, 5, 2, 2, 2, 2, 2,
+	 5, 2, 2, 2, 2, 2, 3, 3, 5, 2, 2, 6, 6, 3, 6, 2,
+	 11, 2, 2, 5, 2, 2, 2, 2, 2, 3, 3, 2, 2, 2, 7, 2,
+	 5, 3, 2, 5, 2, 2, 3, 3, 3, 3, 2, 2, 2, 3, 2, 5,
+	 2, 2, 2, 3, 7, 6, 5, 10, 3, 3, 2, 2, 2, 5, 2, 3,
+	 6, 6, 2, 3, 5, 12, 5, 2, 2, 6, 2, 2, 2, 3, 3, 2,
+	 2, 3, 2, 3, 2, 2, 2, 2, 2, 2, 3, 2, 5, 2, 3, 5,
+	 2, 5, 5, 2, 2, 2, 3, 6, 2, 3, 2, 3, 3, 2, 5, 2,
+	 3, 2, 6, 2, 2, 2, 3, 2, 2, 3, 7, 2, 2, 3, 3, 5,
+	 2, 3, 10, 2, 3, 2, 5, 2, 5, 3, 2, 7, 2, 2, 3, 2
+	 ];
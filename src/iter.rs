@@ -0,0 +1,465 @@
+use core::iter::FusedIterator;
+use core::ops::{Range, RangeInclusive};
+
+use crate::batch::is_prime_batch;
+use crate::wheel::{next_prime, prev_prime, wheel_index_up, WHEEL, WHEEL_MOD};
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::batch::is_prime_batch_128;
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wheel::{next_prime_128, prev_prime_128};
+
+/*
+   Size hints
+
+   The number of primes in [lo,hi) is bounded using
+
+     x/ln(x) < pi(x) < 1.25506*x/ln(x)         Rosser and Schoenfeld, x >= 17 for the lower bound
+     pi(x+y) - pi(x) <= 2y/ln(y)               Montgomery and Vaughan, y > 1
+
+   ln(x) is bounded by ilog2(x)*ln(2) <= ln(x) < (ilog2(x)+1)*ln(2), evaluated in fixed point so that
+   the bounds remain valid in const contexts and across the full 128-bit range.
+*/
+
+/// Skips of at least this many primes are evaluated in blocks by nth
+const SKIP: usize = 32;
+
+/// Wheel candidates per block, 4 turns of the wheel
+const BLOCK: usize = 192;
+
+// ln(2) rounded down and up, scaled by 2^32
+const LN2_LO: u128 = 2977044471;
+const LN2_HI: u128 = 2977044472;
+
+// In: X
+// Out: Lower bound of pi(X)
+const fn pi_lower(x: u128) -> u128 {
+    if x < 17 {
+        // Exact for small values
+        let mut count = 0;
+        let mut idx = 0;
+        let small = [2u128, 3, 5, 7, 11, 13];
+        while idx < 6 {
+            if small[idx] <= x {
+                count += 1;
+            }
+            idx += 1;
+        }
+        return count;
+    }
+    // x/ln(x) > x/((ilog2(x)+1)*ln2)
+    let denom = (x.ilog2() as u128 + 1) * LN2_HI;
+    // x < 2^128 so split to avoid overflowing the scaling
+    (x / denom) << 32 | (((x % denom) << 32) / denom)
+}
+
+// In: X
+// Out: Upper bound of pi(X)
+pub(crate) const fn pi_upper(x: u128) -> u128 {
+    if x < 17 {
+        return pi_lower(x);
+    }
+    // 1.25506*x/ln(x) < 1.25506*x/(ilog2(x)*ln2), with 1.25506 < 1 + 1/4 + 1/196
+    let denom = x.ilog2() as u128 * LN2_LO;
+    let est = (x / denom) << 32 | (((x % denom) << 32) / denom);
+    // Rounding of est and its fractions
+    est + est / 4 + est / 196 + 2
+}
+
+// In: Interval length Y
+// Out: Upper bound of the number of primes in an interval of length Y
+const fn interval_upper(y: u128) -> u128 {
+    if y < 4 {
+        return y;
+    }
+    // 2y/ln(y) < 2y/(ilog2(y)*ln2)
+    let denom = y.ilog2() as u128 * LN2_LO;
+    let est = (y / denom) << 33 | (((y % denom) << 33) / denom);
+    // At most every odd integer plus 2
+    let odd = y / 2 + 1;
+    if est + 1 < odd {
+        est + 1
+    } else {
+        odd
+    }
+}
+
+// In: [lo,hi)
+// Out: Bounds on the number of primes in [lo,hi)
+const fn prime_count_bounds(lo: u128, hi: u128) -> (usize, Option<usize>) {
+    if hi <= lo {
+        return (0, Some(0));
+    }
+    let below = if lo == 0 { 0 } else { pi_upper(lo - 1) };
+    let total = pi_lower(hi - 1);
+    let lower = total.saturating_sub(below);
+
+    let top = pi_upper(hi - 1);
+    let bottom = if lo == 0 { 0 } else { pi_lower(lo - 1) };
+    // pi_lower(lo-1) <= pi(lo-1) <= pi(hi-1) <= top
+    let upper_pi = top - bottom;
+    let upper_interval = interval_upper(hi - lo);
+    let upper = if upper_pi < upper_interval { upper_pi } else { upper_interval };
+
+    let lower = if lower > usize::MAX as u128 { usize::MAX } else { lower as usize };
+    let upper = if upper > usize::MAX as u128 { None } else { Some(upper as usize) };
+    (lower, upper)
+}
+
+/// Iterator over the primes in an interval of 64-bit integers
+///
+/// Primes are yielded in increasing order from the front and decreasing order from the back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Primes {
+    // Remaining interval [lo,hi)
+    lo: u64,
+    hi: u64,
+}
+
+impl Primes {
+    /// Primes in [lo,hi)
+    pub const fn new(lo: u64, hi: u64) -> Self {
+        Self { lo, hi }
+    }
+
+    /// Primes in the range
+    pub const fn range(range: Range<u64>) -> Self {
+        Self::new(range.start, range.end)
+    }
+
+    /// Remaining interval that has not been yielded
+    pub const fn remaining(&self) -> Range<u64> {
+        self.lo..self.hi
+    }
+}
+
+impl Iterator for Primes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.lo >= self.hi {
+            return None;
+        }
+        match next_prime(self.lo.saturating_sub(1)) {
+            // next_prime(0) is 2, so lo == 0 and lo == 1 coincide
+            Some(p) if p < self.hi => {
+                self.lo = p + 1;
+                Some(p)
+            }
+            _ => {
+                self.lo = self.hi;
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        prime_count_bounds(self.lo as u128, self.hi as u128)
+    }
+
+    fn nth(&mut self, mut n: usize) -> Option<u64> {
+        // Short skips are cheaper one prime at a time, as a block evaluates candidates past the result
+        while n < SKIP || self.lo <= 7 {
+            let p = self.next()?;
+            if n == 0 {
+                return Some(p);
+            }
+            n -= 1;
+        }
+
+        let mut candidate = [0u64; BLOCK];
+        let mut flag = [false; BLOCK];
+
+        while self.lo < self.hi {
+            // Wheel candidates in [lo,hi), lo > 7 so the primes under 11 are never skipped
+            let mut base = self.lo - self.lo % WHEEL_MOD;
+            let mut idx = wheel_index_up((self.lo % WHEEL_MOD) as u8);
+            let mut len = 0;
+
+            while len < BLOCK {
+                if idx == 48 {
+                    idx = 0;
+                    base = match base.checked_add(WHEEL_MOD) {
+                        Some(base) => base,
+                        None => break,
+                    };
+                }
+                match base.checked_add(WHEEL[idx] as u64) {
+                    Some(x) if x < self.hi => candidate[len] = x,
+                    _ => break,
+                }
+                len += 1;
+                idx += 1;
+            }
+
+            is_prime_batch(&candidate[..len], &mut flag[..len]);
+
+            for i in 0..len {
+                if flag[i] {
+                    if n == 0 {
+                        self.lo = candidate[i] + 1;
+                        return Some(candidate[i]);
+                    }
+                    n -= 1;
+                }
+            }
+            if len < BLOCK {
+                break;
+            }
+            self.lo = candidate[len - 1] + 1;
+        }
+        self.lo = self.hi;
+        None
+    }
+
+    fn last(mut self) -> Option<u64> {
+        self.next_back()
+    }
+
+    fn min(mut self) -> Option<u64> {
+        self.next()
+    }
+
+    fn max(mut self) -> Option<u64> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for Primes {
+    fn next_back(&mut self) -> Option<u64> {
+        if self.lo >= self.hi {
+            return None;
+        }
+        match prev_prime(self.hi) {
+            Some(p) if p >= self.lo => {
+                self.hi = p;
+                Some(p)
+            }
+            _ => {
+                self.hi = self.lo;
+                None
+            }
+        }
+    }
+}
+
+impl FusedIterator for Primes {}
+
+/// Iterator over the primes in an interval of 128-bit integers
+///
+/// Primes are yielded in increasing order from the front and decreasing order from the back.
+#[cfg(any(feature = "wide", feature = "qft"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Primes128 {
+    lo: u128,
+    hi: u128,
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl Primes128 {
+    /// Primes in [lo,hi)
+    pub const fn new(lo: u128, hi: u128) -> Self {
+        Self { lo, hi }
+    }
+
+    /// Primes in the range
+    pub const fn range(range: Range<u128>) -> Self {
+        Self::new(range.start, range.end)
+    }
+
+    /// Remaining interval that has not been yielded
+    pub const fn remaining(&self) -> Range<u128> {
+        self.lo..self.hi
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl Iterator for Primes128 {
+    type Item = u128;
+
+    fn next(&mut self) -> Option<u128> {
+        if self.lo >= self.hi {
+            return None;
+        }
+        match next_prime_128(self.lo.saturating_sub(1)) {
+            Some(p) if p < self.hi => {
+                self.lo = p + 1;
+                Some(p)
+            }
+            _ => {
+                self.lo = self.hi;
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        prime_count_bounds(self.lo, self.hi)
+    }
+
+    fn nth(&mut self, mut n: usize) -> Option<u128> {
+        if self.hi <= 0x10000000000000000 {
+            // Entirely 64-bit, which evaluates faster. 2^64-1 is composite so clamping hi loses no prime
+            let mut narrow = Primes::new(self.lo as u64, self.hi.min(u64::MAX as u128) as u64);
+            let p = narrow.nth(n);
+            self.lo = narrow.lo as u128;
+            return p.map(|p| p as u128);
+        }
+
+        while n < SKIP || self.lo <= 7 {
+            let p = self.next()?;
+            if n == 0 {
+                return Some(p);
+            }
+            n -= 1;
+        }
+
+        const MODULUS: u128 = WHEEL_MOD as u128;
+
+        let mut candidate = [0u128; BLOCK];
+        let mut flag = [false; BLOCK];
+
+        while self.lo < self.hi {
+            let mut base = self.lo - self.lo % MODULUS;
+            let mut idx = wheel_index_up((self.lo % MODULUS) as u8);
+            let mut len = 0;
+
+            while len < BLOCK {
+                if idx == 48 {
+                    idx = 0;
+                    base = match base.checked_add(MODULUS) {
+                        Some(base) => base,
+                        None => break,
+                    };
+                }
+                match base.checked_add(WHEEL[idx] as u128) {
+                    Some(x) if x < self.hi => candidate[len] = x,
+                    _ => break,
+                }
+                len += 1;
+                idx += 1;
+            }
+
+            is_prime_batch_128(&candidate[..len], &mut flag[..len]);
+
+            for i in 0..len {
+                if flag[i] {
+                    if n == 0 {
+                        self.lo = candidate[i] + 1;
+                        return Some(candidate[i]);
+                    }
+                    n -= 1;
+                }
+            }
+            if len < BLOCK {
+                break;
+            }
+            self.lo = candidate[len - 1] + 1;
+        }
+        self.lo = self.hi;
+        None
+    }
+
+    fn last(mut self) -> Option<u128> {
+        self.next_back()
+    }
+
+    fn min(mut self) -> Option<u128> {
+        self.next()
+    }
+
+    fn max(mut self) -> Option<u128> {
+        self.next_back()
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl DoubleEndedIterator for Primes128 {
+    fn next_back(&mut self) -> Option<u128> {
+        if self.lo >= self.hi {
+            return None;
+        }
+        match prev_prime_128(self.hi) {
+            Some(p) if p >= self.lo => {
+                self.hi = p;
+                Some(p)
+            }
+            _ => {
+                self.hi = self.lo;
+                None
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl FusedIterator for Primes128 {}
+
+/// Iterate over the primes in a range
+///
+/// ```ignore
+/// use machine_prime::PrimeRange;
+///
+/// let count = (1u64 << 40..(1 << 40) + 1000).primes().count();
+/// ```
+pub trait PrimeRange {
+    /// Iterator type
+    type Primes: DoubleEndedIterator;
+
+    /// Primes in the range
+    fn primes(self) -> Self::Primes;
+}
+
+impl PrimeRange for Range<u64> {
+    type Primes = Primes;
+
+    fn primes(self) -> Primes {
+        Primes::range(self)
+    }
+}
+
+impl PrimeRange for RangeInclusive<u64> {
+    type Primes = Primes;
+
+    fn primes(self) -> Primes {
+        // u64::MAX is composite so it can be excluded
+        let hi = if *self.end() == u64::MAX { u64::MAX } else { self.end() + 1 };
+        Primes::new(*self.start(), hi)
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl PrimeRange for Range<u128> {
+    type Primes = Primes128;
+
+    fn primes(self) -> Primes128 {
+        Primes128::range(self)
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl PrimeRange for RangeInclusive<u128> {
+    type Primes = Primes128;
+
+    fn primes(self) -> Primes128 {
+        // u128::MAX is composite so it can be excluded
+        let hi = if *self.end() == u128::MAX { u128::MAX } else { self.end() + 1 };
+        Primes128::new(*self.start(), hi)
+    }
+}
+
+#[cfg(all(test, any(feature = "wide", feature = "qft"), any(feature = "lucas", feature = "ssmr")))]
+mod tests {
+    use super::Primes128;
+
+    #[test]
+    fn nth_bound_2_64() {
+        assert_eq!(Primes128::new(10, 1 << 64).nth(1), Some(13));
+        assert_eq!(Primes128::new(0, 1 << 64).nth(5), Some(13));
+
+        let mut last = Primes128::new(18446744073709551500, 1 << 64);
+        assert_eq!(last.nth(2), Some(18446744073709551557));
+        assert_eq!(last.next(), None);
+    }
+}
@@ -19,26 +19,29 @@
 //! - Base-2 strong fermat test
 //! - Look-up table of 262144 candidate bases for a strong fermat test
 //! - Branches for n < 2^47 to use a single strong fermat test
+//! - Branches for n < 2^32 to 32-bit arithmetic and a look-up table of 4096 bases
 //!
 //! Properties
 //! - is_prime complexity: n < 2^47 0.154; n > 2^47 0.167
 //! - is_prime_wc complexity: n < 2^47 1; n > 2^47 2.0
-//! - Data Memory: 525472 bytes
+//! - Data Memory: 530592 bytes
 //! # Lucas
 //! Algorithm 
 //! - Trial Division by first 129 primes
 //! - Base-2 strong fermat test
 //! - Lucas sequence test using a look-up table of parameters
+//! - Strong fermat test to the bases 2,7 and 61 in 32-bit arithmetic for n < 2^32
 //!
 //! Properties
 //! - is_prime complexity:  0.2
 //! - is_prime_wc complexity: 2.5
-//! - Data Memory: 1056 bytes
+//! - Data Memory: 2080 bytes
 //! # Tiny/No feature
 //! Algorithm 
 //! - Divison by 2
 //! - Base-2 strong fermat test
 //! - Lucas sequence test using parameters calculated over 2Z+1
+//! - Strong fermat test to the bases 2,7 and 61 in 32-bit arithmetic for n < 2^32
 //!
 //! Properties
 //! - is_prime complexity: 0.6
@@ -63,6 +66,11 @@
 //! one can use the strong_fermat test exposed by the "internal" feature, to add more tests at some extra cost. Conversely using
 //! QFT can be more efficient.
 //!
+//! # SIMD
+//! Evaluates the strong fermat tests of is_prime_batch 8 lanes at a time using core::simd. Results are identical to the
+//! scalar functions. 64-bit lane products are emulated from 32-bit products so this is only profitable on targets with
+//! wide vector multipliers, e.g AVX-512 enabled with `-C target-cpu=native`.
+//!
 //! # QFT
 //! Algorithm
 //! - Division by first 139 primes (if Lucas or SSMR feature is enabled)
@@ -75,43 +83,138 @@
 //!
 //! This algorithm is substantially stronger than the BPSW, and exists as a fallback algorithm incase a BPSW is discovered or the user 
 //! wants greater confidence in accuracy. It however has not been proven to be correct for all inputs and errors may exist.
+//!
+//! Without the SSMR or Lucas features, QFT also replaces the Lucas test of is_prime and is_prime_wc for 2^32 < n < 2^64, giving the
+//! table-free build a base-2 strong test followed by Khashin's QFT.
+//!
+//! is_prime_qft applies the same base-2 strong test and QFT to n < 2^64 in every build, without trial division or look-up tables.
+//! It is independent of the SSMR and Lucas algorithms and can be used to cross-check them.
 
 
 #![no_std]
 #![allow(internal_features)]
-#![feature(lang_items)]
+#![cfg_attr(not(test), feature(lang_items))]
+#![cfg_attr(feature="simd", feature(portable_simd))]
 
 
 pub(crate) mod check;
 pub(crate) mod hashbase;
 pub(crate) mod primes;
+pub(crate) mod narrow;
+pub(crate) mod cert;
+pub(crate) mod factor;
+pub(crate) mod power;
+pub(crate) mod multiplicative;
+pub(crate) mod order;
+pub(crate) mod sqrt;
+pub(crate) mod symbol;
+pub(crate) mod montgomery;
+pub(crate) mod lucas_test;
+pub(crate) mod qft_64;
+pub(crate) mod context;
+pub(crate) mod traits;
+pub(crate) mod batch;
+pub(crate) mod wheel;
+pub(crate) mod iter;
+pub(crate) mod sieve;
+pub(crate) mod count;
+pub(crate) mod progression;
+pub(crate) mod constellation;
+pub(crate) mod safe;
+pub(crate) mod random;
+#[cfg(feature="simd")]
+pub(crate) mod simd;
 
 #[cfg(any(feature="wide",feature="qft"))]
 pub(crate) mod wide;
-#[cfg(feature="qft")]
+#[cfg(any(feature="wide",feature="qft"))]
+pub(crate) mod factor_128;
+#[cfg(any(feature="wide",feature="qft"))]
 pub(crate) mod qft;
 
 pub use check::{is_prime,is_prime_wc};
+pub use qft_64::is_prime_qft;
+pub use context::PrimalityContext;
+pub use narrow::{is_prime_32,is_prime_wc_32};
+pub use traits::Primality;
+pub use batch::is_prime_batch;
+pub use wheel::{next_prime,prev_prime};
+pub use iter::{Primes,PrimeRange};
+pub use progression::ResiduePrimes;
+pub use constellation::{Constellation,Constellations,is_admissible,TWIN_PRIMES,COUSIN_PRIMES,SEXY_PRIMES,PRIME_TRIPLETS,PRIME_TRIPLETS_ALT,PRIME_QUADRUPLETS};
+pub use safe::{next_safe_prime,next_sophie_germain,sophie_germain_bitmap};
+pub use random::{RandomSource,SplitMix64,PrimeSampler,random_prime,random_prime_in};
+pub use sieve::{prime_bitmap,prime_count_interval};
+pub use count::{prime_pi,prime_sum,prime_pi_workspace,nth_prime,nth_prime_workspace};
+pub use factor::{factor,Factorization,FactorIter,FACTOR_CAPACITY};
+pub use power::{iroot,is_perfect_power,is_prime_power};
+pub use multiplicative::{Divisors,totient,carmichael,sigma,divisor_count,moebius,liouville,radical,is_squarefree,divisors};
+pub use order::{multiplicative_order,primitive_root,is_primitive_root};
+pub use sqrt::{sqrt_mod_prime,sqrt_mod_prime_power,sqrt_mod,crt};
+pub use symbol::{jacobi,kronecker,legendre};
+pub use montgomery::{Montgomery64,MontResidue};
+pub use lucas_test::{LucasParams,strong_lucas,extra_strong_lucas,almost_extra_strong_lucas};
+pub use cert::{Certificate,ParseCertificateError,CERT_CAPACITY,prime_certificate,verify_certificate};
 #[cfg(any(feature="wide",feature="qft"))]
 pub use wide::{is_prime_128,is_prime_wc_128};
+#[cfg(any(feature="wide",feature="qft"))]
+pub use batch::is_prime_batch_128;
+#[cfg(any(feature="wide",feature="qft"))]
+pub use wheel::{next_prime_128,prev_prime_128};
+#[cfg(any(feature="wide",feature="qft"))]
+pub use iter::Primes128;
+#[cfg(any(feature="wide",feature="qft"))]
+pub use progression::ResiduePrimes128;
+#[cfg(any(feature="wide",feature="qft"))]
+pub use constellation::Constellations128;
+#[cfg(any(feature="wide",feature="qft"))]
+pub use safe::{next_safe_prime_128,next_sophie_germain_128,sophie_germain_bitmap_128};
+#[cfg(any(feature="wide",feature="qft"))]
+pub use random::{PrimeSampler128,random_prime_128,random_prime_in_128};
+#[cfg(any(feature="wide",feature="qft"))]
+pub use power::{iroot_128,is_perfect_power_128,is_prime_power_128};
+#[cfg(any(feature="wide",feature="qft"))]
+pub use multiplicative::{Divisors128,totient_128,carmichael_128,sigma_128,divisor_count_128,moebius_128,liouville_128,radical_128,is_squarefree_128,divisors_128};
+#[cfg(any(feature="wide",feature="qft"))]
+pub use order::{multiplicative_order_128,primitive_root_128,is_primitive_root_128};
+#[cfg(any(feature="wide",feature="qft"))]
+pub use sqrt::{sqrt_mod_prime_128,sqrt_mod_prime_power_128,sqrt_mod_128,crt_128};
+#[cfg(any(feature="wide",feature="qft"))]
+pub use symbol::{jacobi_128,kronecker_128,legendre_128};
+#[cfg(any(feature="wide",feature="qft"))]
+pub use montgomery::{Montgomery128,MontResidue128};
+#[cfg(any(feature="wide",feature="qft"))]
+pub use lucas_test::{strong_lucas_128,extra_strong_lucas_128,almost_extra_strong_lucas_128};
+#[cfg(any(feature="wide",feature="qft"))]
+pub use context::PrimalityContext128;
+#[cfg(any(feature="wide",feature="qft"))]
+pub use factor_128::{factor_128,Factorization128,FactorIter128,FACTOR_CAPACITY_128};
 
 #[cfg(feature="internal")]
 pub use check::*;
+#[cfg(feature="internal")]
+pub use narrow::*;
+#[cfg(feature="internal")]
+pub use qft_64::qft_64;
+#[cfg(all(feature="internal",feature="simd"))]
+pub use simd::*;
 #[cfg(all(feature="internal",feature="wide"))]
 pub use wide::*;
 #[cfg(all(feature="internal",any(feature="lucas",feature="ssmr")))]
 pub use primes::*;
 #[cfg(all(feature="internal",feature="ssmr"))]
-pub use hashbase::FERMAT_WITNESS;
+pub use hashbase::{FERMAT_WITNESS,FERMAT_WITNESS_32};
 
  // Comment out for crates publication
 
+#[cfg(not(test))]
 #[panic_handler]
 fn panic(_info: &core::panic::PanicInfo) -> ! {
     loop {}
 }
 
 
+#[cfg(not(test))]
 #[lang = "eh_personality"]
 extern "C" fn eh_personality() {}
 
@@ -0,0 +1,431 @@
+use crate::check::{add_mod, half_mod, mont_prod, mont_sub, mul_inv2_newton, one_mont, signed_mod, to_mont, two_mont};
+use crate::factor::gcd;
+use crate::symbol::jacobi;
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::factor_128::gcd_128;
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::symbol::jacobi_128;
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wide::{add_mod_128, half_mod_128, mont_prod_128, mont_sqr_128, mont_sub_128, mul_inv2_128, one_mont_128, signed_mod_128, to_mont_128, two_mont_128};
+
+/*
+   Lucas probable prime tests
+
+   For D = P^2 - 4Q with (D/N) = ±1, write N - (D/N) = d 2^s with d odd. N is a strong Lucas probable prime if U_d ≡ 0
+   or V_(d 2^r) ≡ 0 (mod N) for some 0 <= r < s. Every parameter selection method gives (D/N) = -1, other parameters
+   are used by PrimalityContext. U and V are computed together by doubling and incrementing the index,
+   U_2k = U_k V_k, V_2k = V_k^2 - 2Q^k, U_(k+1) = (PU_k + V_k)/2, V_(k+1) = (DU_k + PV_k)/2.
+
+   With Q = 1 the V sequence alone is computed by the ladder V_2k = V_k^2 - 2, V_(2k+1) = V_k V_(k+1) - P. N is an extra
+   strong Lucas probable prime if U_d ≡ 0 and V_d ≡ ±2, or V_(d 2^r) ≡ 0 for some 0 <= r < s-1. U_d is not computed,
+   since DU_d = 2V_(d+1) - PV_d and D is coprime to N. The almost extra strong test drops the condition on U_d.
+
+   The tests accept every N, so a Jacobi symbol of 0 for D coprime to a composite N, a perfect square N (for which
+   no D exists) and N even all return false. 2^64-1 and 2^128-1 are divisible by 5, the first D of every method,
+   so N+1 never overflows.
+*/
+
+/// Parameter selection for the strong Lucas test
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LucasParams {
+    /// Selfridge's method A, the first D in 5, -7, 9, -11, ... with (D/N) = -1, P = 1 and Q = (1-D)/4
+    SelfridgeA,
+    /// Method A*, method A except that Q = -1 is replaced by P = Q = 5
+    SelfridgeAStar,
+    /// Baillie's method C, the first P in 3, 4, 5, ... with (P^2-4/N) = -1 and Q = 1
+    BaillieC,
+}
+
+// In: N odd, not a square
+// Out: (P,Q,D) by method A, None if N is found to be composite
+const fn selfridge(n: u64, star: bool) -> Option<(i64, i64, i64)> {
+    let mut d: i64 = 5;
+    loop {
+        let residue = signed_mod(d as i128, n);
+        match jacobi(residue, n) {
+            -1 => break,
+            // N divides D only if N is smaller than |D|, and then N may still be prime
+            0 if residue != 0 => return None,
+            _ => {}
+        }
+        d = if d > 0 { -d - 2 } else { -d + 2 };
+    }
+    let q = (1 - d) / 4;
+    if star && q == -1 {
+        return Some((5, 5, 5));
+    }
+    if gcd(q.unsigned_abs() % n, n) != 1 {
+        return None;
+    }
+    Some((1, q, d))
+}
+
+// In: N odd, not a square, step > 0
+// Out: First P in 3, 3 + step, ... with (P^2-4/N) = -1, None if N is found to be composite
+const fn baillie(n: u64, step: u64) -> Option<u64> {
+    let mut p = 3u64;
+    loop {
+        let d = ((p as u128 * p as u128 - 4) % n as u128) as u64;
+        match jacobi(d, n) {
+            -1 => return Some(p),
+            0 if d != 0 => return None,
+            _ => {}
+        }
+        p += step;
+    }
+}
+
+// In: N
+// Out: True if N is even, a square or less than 2, the cases where no Lucas test is performed
+const fn trivial(n: u64) -> bool {
+    n < 2 || n & 1 == 0 || n.isqrt() * n.isqrt() == n
+}
+
+// In: N odd, N^-1, Mont(1,N), M = N - (D/N), P,Q,D < N with (D/N) = ±1 and gcd(Q,N) = 1
+// Out: True if N is a strong Lucas probable prime
+pub(crate) const fn strong(n: u64, inv: u64, one: u64, m: u64, p: u64, q: u64, d: u64) -> bool {
+    let two = two_mont(one, n);
+    let p = to_mont(p, n);
+    let q = to_mont(q, n);
+    let disc = to_mont(d, n);
+
+    let s = m.trailing_zeros();
+    let d = m >> s;
+
+    // U_1, V_1, Q^1, then the bits of d after the leading one
+    let (mut u, mut v, mut qk) = (one, p, q);
+    let mut bit = u64::BITS - 1 - d.leading_zeros();
+    while bit > 0 {
+        bit -= 1;
+        u = mont_prod(u, v, inv, n);
+        v = mont_sub(mont_prod(v, v, inv, n), mont_prod(two, qk, inv, n), n);
+        qk = mont_prod(qk, qk, inv, n);
+        if (d >> bit) & 1 == 1 {
+            let pu = mont_prod(p, u, inv, n);
+            let pv = mont_prod(p, v, inv, n);
+            let du = mont_prod(disc, u, inv, n);
+            u = half_mod(add_mod(pu, v, n), n);
+            v = half_mod(add_mod(du, pv, n), n);
+            qk = mont_prod(qk, q, inv, n);
+        }
+    }
+    if u == 0 || v == 0 {
+        return true;
+    }
+    let mut r = 1;
+    while r < s {
+        v = mont_sub(mont_prod(v, v, inv, n), mont_prod(two, qk, inv, n), n);
+        if v == 0 {
+            return true;
+        }
+        qk = mont_prod(qk, qk, inv, n);
+        r += 1;
+    }
+    false
+}
+
+// In: N odd, not a square, P with (P^2-4/N) = -1
+// Out: True if N is an extra strong Lucas probable prime, or almost extra strong if EXTRA is false
+const fn extra_strong(n: u64, p: u64, extra: bool) -> bool {
+    let inv = mul_inv2_newton(n);
+    let one = one_mont(n);
+    let two = two_mont(one, n);
+    let minus_two = mont_sub(0, two, n);
+    let p = to_mont(p % n, n);
+
+    let n_plus = n + 1;
+    let s = n_plus.trailing_zeros();
+    let d = n_plus >> s;
+
+    // (V_k, V_(k+1)) starting from (V_0, V_1)
+    let (mut v, mut w) = (two, p);
+    let mut bit = u64::BITS - d.leading_zeros();
+    while bit > 0 {
+        bit -= 1;
+        let t = mont_sub(mont_prod(v, w, inv, n), p, n);
+        if (d >> bit) & 1 == 1 {
+            v = t;
+            w = mont_sub(mont_prod(w, w, inv, n), two, n);
+        } else {
+            w = t;
+            v = mont_sub(mont_prod(v, v, inv, n), two, n);
+        }
+    }
+    // U_d ≡ 0 if and only if 2V_(d+1) ≡ PV_d
+    if (v == two || v == minus_two) && (!extra || add_mod(w, w, n) == mont_prod(p, v, inv, n)) {
+        return true;
+    }
+    let mut r = 0;
+    while r + 1 < s {
+        if v == 0 {
+            return true;
+        }
+        v = mont_sub(mont_prod(v, v, inv, n), two, n);
+        r += 1;
+    }
+    false
+}
+
+/// Strong Lucas probable prime test
+///
+/// In: N, parameter selection method
+///
+/// Out: True if N is 2 or a strong Lucas probable prime
+pub const fn strong_lucas(n: u64, params: LucasParams) -> bool {
+    if trivial(n) {
+        return n == 2;
+    }
+    let found = match params {
+        LucasParams::SelfridgeA => selfridge(n, false),
+        LucasParams::SelfridgeAStar => selfridge(n, true),
+        LucasParams::BaillieC => match baillie(n, 1) {
+            Some(p) => Some((p as i64, 1, p as i64 * p as i64 - 4)),
+            None => None,
+        },
+    };
+    match found {
+        Some((p, q, d)) => {
+            let (p, q, d) = (signed_mod(p as i128, n), signed_mod(q as i128, n), signed_mod(d as i128, n));
+            strong(n, mul_inv2_newton(n), one_mont(n), n + 1, p, q, d)
+        }
+        None => false,
+    }
+}
+
+/// Extra strong Lucas probable prime test
+///
+/// In: N, step > 0 between the P tried, 1 gives Baillie's method C
+///
+/// Out: True if N is 2 or an extra strong Lucas probable prime with Q = 1 and the first P in 3, 3 + step, ...
+///      such that (P^2-4/N) = -1
+pub const fn extra_strong_lucas(n: u64, step: u64) -> bool {
+    if trivial(n) {
+        return n == 2;
+    }
+    match baillie(n, if step == 0 { 1 } else { step }) {
+        Some(p) => extra_strong(n, p, true),
+        None => false,
+    }
+}
+
+/// Almost extra strong Lucas probable prime test
+///
+/// In: N, step > 0 between the P tried, 1 gives Baillie's method C
+///
+/// Out: True if N is 2 or an almost extra strong Lucas probable prime with Q = 1 and the first P in 3, 3 + step, ...
+///      such that (P^2-4/N) = -1
+pub const fn almost_extra_strong_lucas(n: u64, step: u64) -> bool {
+    if trivial(n) {
+        return n == 2;
+    }
+    match baillie(n, if step == 0 { 1 } else { step }) {
+        Some(p) => extra_strong(n, p, false),
+        None => false,
+    }
+}
+
+// In: N odd, not a square
+// Out: (P,Q,D) by method A, None if N is found to be composite
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn selfridge_128(n: u128, star: bool) -> Option<(i64, i64, i64)> {
+    let mut d: i64 = 5;
+    loop {
+        let residue = signed_mod_128(d as i128, n);
+        match jacobi_128(residue, n) {
+            -1 => break,
+            // N divides D only if N is smaller than |D|, and then N may still be prime
+            0 if residue != 0 => return None,
+            _ => {}
+        }
+        d = if d > 0 { -d - 2 } else { -d + 2 };
+    }
+    let q = (1 - d) / 4;
+    if star && q == -1 {
+        return Some((5, 5, 5));
+    }
+    if gcd_128(q.unsigned_abs() as u128 % n, n) != 1 {
+        return None;
+    }
+    Some((1, q, d))
+}
+
+// In: N odd, not a square, step > 0
+// Out: First P in 3, 3 + step, ... with (P^2-4/N) = -1, None if N is found to be composite
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn baillie_128(n: u128, step: u64) -> Option<u64> {
+    let mut p = 3u64;
+    loop {
+        let d = (p as u128 * p as u128 - 4) % n;
+        match jacobi_128(d, n) {
+            -1 => return Some(p),
+            0 if d != 0 => return None,
+            _ => {}
+        }
+        p += step;
+    }
+}
+
+// In: N > 2^64
+// Out: True if N is even or a square, the cases where no Lucas test is performed
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn trivial_128(n: u128) -> bool {
+    n & 1 == 0 || n.isqrt() * n.isqrt() == n
+}
+
+// In: N odd, N^-1, Mont(1,N), M = N - (D/N), P,Q,D < N with (D/N) = ±1 and gcd(Q,N) = 1
+// Out: True if N is a strong Lucas probable prime
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub(crate) const fn strong_128(n: u128, inv: u128, one: u128, m: u128, p: u128, q: u128, d: u128) -> bool {
+    let two = two_mont_128(one, n);
+    let p = to_mont_128(p, n);
+    let q = to_mont_128(q, n);
+    let disc = to_mont_128(d, n);
+
+    let s = m.trailing_zeros();
+    let d = m >> s;
+
+    // U_1, V_1, Q^1, then the bits of d after the leading one
+    let (mut u, mut v, mut qk) = (one, p, q);
+    let mut bit = u128::BITS - 1 - d.leading_zeros();
+    while bit > 0 {
+        bit -= 1;
+        u = mont_prod_128(u, v, inv, n);
+        v = mont_sub_128(mont_sqr_128(v, inv, n), mont_prod_128(two, qk, inv, n), n);
+        qk = mont_sqr_128(qk, inv, n);
+        if (d >> bit) & 1 == 1 {
+            let pu = mont_prod_128(p, u, inv, n);
+            let pv = mont_prod_128(p, v, inv, n);
+            let du = mont_prod_128(disc, u, inv, n);
+            u = half_mod_128(add_mod_128(pu, v, n), n);
+            v = half_mod_128(add_mod_128(du, pv, n), n);
+            qk = mont_prod_128(qk, q, inv, n);
+        }
+    }
+    if u == 0 || v == 0 {
+        return true;
+    }
+    let mut r = 1;
+    while r < s {
+        v = mont_sub_128(mont_sqr_128(v, inv, n), mont_prod_128(two, qk, inv, n), n);
+        if v == 0 {
+            return true;
+        }
+        qk = mont_sqr_128(qk, inv, n);
+        r += 1;
+    }
+    false
+}
+
+// In: N odd, not a square, P with (P^2-4/N) = -1
+// Out: True if N is an extra strong Lucas probable prime, or almost extra strong if EXTRA is false
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn extra_strong_128(n: u128, p: u64, extra: bool) -> bool {
+    let inv = mul_inv2_128(n);
+    let one = one_mont_128(n);
+    let two = two_mont_128(one, n);
+    let minus_two = mont_sub_128(0, two, n);
+    let p = to_mont_128(p as u128 % n, n);
+
+    let n_plus = n + 1;
+    let s = n_plus.trailing_zeros();
+    let d = n_plus >> s;
+
+    // (V_k, V_(k+1)) starting from (V_0, V_1)
+    let (mut v, mut w) = (two, p);
+    let mut bit = u128::BITS - d.leading_zeros();
+    while bit > 0 {
+        bit -= 1;
+        let t = mont_sub_128(mont_prod_128(v, w, inv, n), p, n);
+        if (d >> bit) & 1 == 1 {
+            v = t;
+            w = mont_sub_128(mont_sqr_128(w, inv, n), two, n);
+        } else {
+            w = t;
+            v = mont_sub_128(mont_sqr_128(v, inv, n), two, n);
+        }
+    }
+    // U_d ≡ 0 if and only if 2V_(d+1) ≡ PV_d
+    if (v == two || v == minus_two) && (!extra || add_mod_128(w, w, n) == mont_prod_128(p, v, inv, n)) {
+        return true;
+    }
+    let mut r = 0;
+    while r + 1 < s {
+        if v == 0 {
+            return true;
+        }
+        v = mont_sub_128(mont_sqr_128(v, inv, n), two, n);
+        r += 1;
+    }
+    false
+}
+
+/// Strong Lucas probable prime test, 128-bit form
+///
+/// In: N, parameter selection method
+///
+/// Out: True if N is 2 or a strong Lucas probable prime
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn strong_lucas_128(n: u128, params: LucasParams) -> bool {
+    if n <= u64::MAX as u128 {
+        return strong_lucas(n as u64, params);
+    }
+    if trivial_128(n) {
+        return false;
+    }
+    let found = match params {
+        LucasParams::SelfridgeA => selfridge_128(n, false),
+        LucasParams::SelfridgeAStar => selfridge_128(n, true),
+        LucasParams::BaillieC => match baillie_128(n, 1) {
+            Some(p) => Some((p as i64, 1, p as i64 * p as i64 - 4)),
+            None => None,
+        },
+    };
+    match found {
+        Some((p, q, d)) => {
+            let (p, q, d) = (signed_mod_128(p as i128, n), signed_mod_128(q as i128, n), signed_mod_128(d as i128, n));
+            strong_128(n, mul_inv2_128(n), one_mont_128(n), n + 1, p, q, d)
+        }
+        None => false,
+    }
+}
+
+/// Extra strong Lucas probable prime test, 128-bit form
+///
+/// In: N, step > 0 between the P tried, 1 gives Baillie's method C
+///
+/// Out: True if N is 2 or an extra strong Lucas probable prime with Q = 1 and the first P in 3, 3 + step, ...
+///      such that (P^2-4/N) = -1
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn extra_strong_lucas_128(n: u128, step: u64) -> bool {
+    if n <= u64::MAX as u128 {
+        return extra_strong_lucas(n as u64, step);
+    }
+    if trivial_128(n) {
+        return false;
+    }
+    match baillie_128(n, if step == 0 { 1 } else { step }) {
+        Some(p) => extra_strong_128(n, p, true),
+        None => false,
+    }
+}
+
+/// Almost extra strong Lucas probable prime test, 128-bit form
+///
+/// In: N, step > 0 between the P tried, 1 gives Baillie's method C
+///
+/// Out: True if N is 2 or an almost extra strong Lucas probable prime with Q = 1 and the first P in 3, 3 + step, ...
+///      such that (P^2-4/N) = -1
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn almost_extra_strong_lucas_128(n: u128, step: u64) -> bool {
+    if n <= u64::MAX as u128 {
+        return almost_extra_strong_lucas(n as u64, step);
+    }
+    if trivial_128(n) {
+        return false;
+    }
+    match baillie_128(n, if step == 0 { 1 } else { step }) {
+        Some(p) => extra_strong_128(n, p, false),
+        None => false,
+    }
+}
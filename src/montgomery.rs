@@ -0,0 +1,283 @@
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::check::{add_mod, mont_pow, mont_prod, mont_sub, mul_inv2_newton, one_mont, to_mont};
+use crate::sqrt::mod_inverse;
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wide::{add_mod_128, mont_pow_128, mont_prod_128, mont_sqr_128, mont_sub_128, mul_inv2_128, one_mont_128, to_mont_128};
+
+/*
+   Montgomery contexts
+
+   A context caches N^-1 over Z/2^k, R mod N and R^2 mod N for R = 2^k, k = 64 or 128. Residues hold Mont(X,N) = XR mod N
+   and a reference to their context, so the modulus and inverse cannot be passed in the wrong order or mixed between
+   moduli. Conversion in is a single Montgomery product with R^2, conversion out a product with 1.
+
+   Combining residues of different contexts is a logic error, checked only in debug builds.
+*/
+
+/// Montgomery arithmetic modulo an odd N < 2^64
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Montgomery64 {
+    n: u64,
+    inv: u64,
+    one: u64,
+    r2: u64,
+}
+
+/// Residue modulo N in Montgomery form
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MontResidue<'a> {
+    ctx: &'a Montgomery64,
+    value: u64,
+}
+
+impl Montgomery64 {
+    /// Context for an odd modulus
+    ///
+    /// Out: None if N is even
+    pub const fn new(n: u64) -> Option<Self> {
+        if n & 1 == 0 {
+            return None;
+        }
+        let one = one_mont(n);
+        Some(Self { n, inv: mul_inv2_newton(n), one, r2: to_mont(one, n) })
+    }
+
+    /// Modulus N
+    pub const fn modulus(&self) -> u64 {
+        self.n
+    }
+
+    /// Residue of X modulo N
+    pub const fn residue(&self, x: u64) -> MontResidue<'_> {
+        MontResidue { ctx: self, value: mont_prod(x % self.n, self.r2, self.inv, self.n) }
+    }
+
+    /// Residue with Montgomery representation X < N
+    pub const fn from_montgomery(&self, x: u64) -> MontResidue<'_> {
+        MontResidue { ctx: self, value: x % self.n }
+    }
+
+    /// Zero modulo N
+    pub const fn zero(&self) -> MontResidue<'_> {
+        MontResidue { ctx: self, value: 0 }
+    }
+
+    /// One modulo N
+    pub const fn one(&self) -> MontResidue<'_> {
+        MontResidue { ctx: self, value: self.one }
+    }
+}
+
+impl<'a> MontResidue<'a> {
+    /// Context of the residue
+    pub const fn context(self) -> &'a Montgomery64 {
+        self.ctx
+    }
+
+    /// Least non-negative integer representing the residue
+    pub const fn value(self) -> u64 {
+        mont_prod(self.value, 1, self.ctx.inv, self.ctx.n)
+    }
+
+    /// Montgomery representation XR mod N
+    pub const fn montgomery(self) -> u64 {
+        self.value
+    }
+
+    /// Square of the residue
+    pub const fn square(self) -> Self {
+        Self { ctx: self.ctx, value: mont_prod(self.value, self.value, self.ctx.inv, self.ctx.n) }
+    }
+
+    /// Residue raised to the power E, X^0 = 1
+    pub const fn pow(self, e: u64) -> Self {
+        if e == 0 {
+            return self.ctx.one();
+        }
+        Self { ctx: self.ctx, value: mont_pow(self.value, self.ctx.one, e, self.ctx.inv, self.ctx.n) }
+    }
+
+    /// Multiplicative inverse
+    ///
+    /// Out: None if gcd(X,N) != 1
+    pub const fn inverse(self) -> Option<Self> {
+        match mod_inverse(self.value() as u128, self.ctx.n as u128) {
+            Some(inv) => Some(self.ctx.residue(inv as u64)),
+            None => None,
+        }
+    }
+}
+
+impl<'a> Add for MontResidue<'a> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        debug_assert!(self.ctx.n == rhs.ctx.n, "residues of different moduli");
+        Self { ctx: self.ctx, value: add_mod(self.value, rhs.value, self.ctx.n) }
+    }
+}
+
+impl<'a> Sub for MontResidue<'a> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        debug_assert!(self.ctx.n == rhs.ctx.n, "residues of different moduli");
+        Self { ctx: self.ctx, value: mont_sub(self.value, rhs.value, self.ctx.n) }
+    }
+}
+
+impl<'a> Mul for MontResidue<'a> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        debug_assert!(self.ctx.n == rhs.ctx.n, "residues of different moduli");
+        Self { ctx: self.ctx, value: mont_prod(self.value, rhs.value, self.ctx.inv, self.ctx.n) }
+    }
+}
+
+impl<'a> Neg for MontResidue<'a> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self { ctx: self.ctx, value: mont_sub(0, self.value, self.ctx.n) }
+    }
+}
+
+/// Montgomery arithmetic modulo an odd N < 2^128
+#[cfg(any(feature = "wide", feature = "qft"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Montgomery128 {
+    n: u128,
+    inv: u128,
+    one: u128,
+    r2: u128,
+}
+
+/// Residue modulo N in Montgomery form, 128-bit form
+#[cfg(any(feature = "wide", feature = "qft"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MontResidue128<'a> {
+    ctx: &'a Montgomery128,
+    value: u128,
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl Montgomery128 {
+    /// Context for an odd modulus
+    ///
+    /// Out: None if N is even
+    pub const fn new(n: u128) -> Option<Self> {
+        if n & 1 == 0 {
+            return None;
+        }
+        let one = one_mont_128(n);
+        Some(Self { n, inv: mul_inv2_128(n), one, r2: to_mont_128(one, n) })
+    }
+
+    /// Modulus N
+    pub const fn modulus(&self) -> u128 {
+        self.n
+    }
+
+    /// Residue of X modulo N
+    pub const fn residue(&self, x: u128) -> MontResidue128<'_> {
+        MontResidue128 { ctx: self, value: mont_prod_128(x % self.n, self.r2, self.inv, self.n) }
+    }
+
+    /// Residue with Montgomery representation X < N
+    pub const fn from_montgomery(&self, x: u128) -> MontResidue128<'_> {
+        MontResidue128 { ctx: self, value: x % self.n }
+    }
+
+    /// Zero modulo N
+    pub const fn zero(&self) -> MontResidue128<'_> {
+        MontResidue128 { ctx: self, value: 0 }
+    }
+
+    /// One modulo N
+    pub const fn one(&self) -> MontResidue128<'_> {
+        MontResidue128 { ctx: self, value: self.one }
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl<'a> MontResidue128<'a> {
+    /// Context of the residue
+    pub const fn context(self) -> &'a Montgomery128 {
+        self.ctx
+    }
+
+    /// Least non-negative integer representing the residue
+    pub const fn value(self) -> u128 {
+        mont_prod_128(self.value, 1, self.ctx.inv, self.ctx.n)
+    }
+
+    /// Montgomery representation XR mod N
+    pub const fn montgomery(self) -> u128 {
+        self.value
+    }
+
+    /// Square of the residue
+    pub const fn square(self) -> Self {
+        Self { ctx: self.ctx, value: mont_sqr_128(self.value, self.ctx.inv, self.ctx.n) }
+    }
+
+    /// Residue raised to the power E, X^0 = 1
+    pub const fn pow(self, e: u128) -> Self {
+        if e == 0 {
+            return self.ctx.one();
+        }
+        Self { ctx: self.ctx, value: mont_pow_128(self.value, self.ctx.one, e, self.ctx.inv, self.ctx.n) }
+    }
+
+    /// Multiplicative inverse
+    ///
+    /// Out: None if gcd(X,N) != 1
+    pub const fn inverse(self) -> Option<Self> {
+        match mod_inverse(self.value(), self.ctx.n) {
+            Some(inv) => Some(self.ctx.residue(inv)),
+            None => None,
+        }
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl<'a> Add for MontResidue128<'a> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        debug_assert!(self.ctx.n == rhs.ctx.n, "residues of different moduli");
+        Self { ctx: self.ctx, value: add_mod_128(self.value, rhs.value, self.ctx.n) }
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl<'a> Sub for MontResidue128<'a> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        debug_assert!(self.ctx.n == rhs.ctx.n, "residues of different moduli");
+        Self { ctx: self.ctx, value: mont_sub_128(self.value, rhs.value, self.ctx.n) }
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl<'a> Mul for MontResidue128<'a> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        debug_assert!(self.ctx.n == rhs.ctx.n, "residues of different moduli");
+        Self { ctx: self.ctx, value: mont_prod_128(self.value, rhs.value, self.ctx.inv, self.ctx.n) }
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl<'a> Neg for MontResidue128<'a> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self { ctx: self.ctx, value: mont_sub_128(0, self.value, self.ctx.n) }
+    }
+}
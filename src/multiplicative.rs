@@ -0,0 +1,603 @@
+use core::iter::FusedIterator;
+
+use crate::factor::{factor, gcd, Factorization, FACTOR_CAPACITY};
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::factor_128::{factor_128, gcd_128, Factorization128, FACTOR_CAPACITY_128};
+
+/*
+   Arithmetic functions
+
+   Each function is evaluated from the prime factorisation, either as a method of Factorization or from N directly.
+   Values that cannot exceed N are returned in the same type. The divisor count of a 128-bit integer is below 2^64 and
+   sigma_k is returned as an Option, None if it overflows u128. By convention every function of 0 is 0, 0 is not
+   squarefree and has no divisors.
+
+   Divisors are generated by a mixed-radix counter over the exponents, so each divisor is a product of the previous one
+   with a single prime or a quotient by a prime power. They are not produced in increasing order.
+*/
+
+// In: A, B > 0 with lcm(A,B) < 2^64
+// Out: lcm(A,B)
+const fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
+
+// In: P, K, E
+// Out: 1 + P^K + P^2K + ... + P^EK, None if it overflows
+const fn geometric_sum(p: u128, k: u32, e: u32) -> Option<u128> {
+    let ratio = match p.checked_pow(k) {
+        Some(r) => r,
+        None => return None,
+    };
+    let mut sum = 1u128;
+    let mut term = 1u128;
+    let mut idx = 0;
+    while idx < e {
+        term = match term.checked_mul(ratio) {
+            Some(t) => t,
+            None => return None,
+        };
+        sum = match sum.checked_add(term) {
+            Some(s) => s,
+            None => return None,
+        };
+        idx += 1;
+    }
+    Some(sum)
+}
+
+impl Factorization {
+    /// Euler's totient, the number of integers in [1,N] coprime to N
+    pub const fn totient(&self) -> u64 {
+        if self.n() == 0 {
+            return 0;
+        }
+        let mut phi = self.n();
+        let mut idx = 0;
+        while let Some((p, _)) = self.get(idx) {
+            phi = phi / p * (p - 1);
+            idx += 1;
+        }
+        phi
+    }
+
+    /// Carmichael's function, the exponent of the multiplicative group modulo N
+    pub const fn carmichael(&self) -> u64 {
+        if self.n() == 0 {
+            return 0;
+        }
+        let mut lambda = 1;
+        let mut idx = 0;
+        while let Some((p, e)) = self.get(idx) {
+            let l = if p == 2 {
+                // The group modulo 2^e is cyclic only for e < 3
+                if e < 3 {
+                    1 << (e - 1)
+                } else {
+                    1 << (e - 2)
+                }
+            } else {
+                p.pow(e - 1) * (p - 1)
+            };
+            lambda = lcm(lambda, l);
+            idx += 1;
+        }
+        lambda
+    }
+
+    /// Sum of the k-th powers of the divisors
+    ///
+    /// Out: sigma_k(N), None if it overflows u128
+    pub const fn sigma(&self, k: u32) -> Option<u128> {
+        if self.n() == 0 {
+            return Some(0);
+        }
+        let mut sum = 1u128;
+        let mut idx = 0;
+        while let Some((p, e)) = self.get(idx) {
+            let term = match geometric_sum(p as u128, k, e) {
+                Some(t) => t,
+                None => return None,
+            };
+            sum = match sum.checked_mul(term) {
+                Some(s) => s,
+                None => return None,
+            };
+            idx += 1;
+        }
+        Some(sum)
+    }
+
+    /// Number of divisors
+    pub const fn divisor_count(&self) -> u64 {
+        if self.n() == 0 {
+            return 0;
+        }
+        let mut count = 1;
+        let mut idx = 0;
+        while let Some((_, e)) = self.get(idx) {
+            count *= e as u64 + 1;
+            idx += 1;
+        }
+        count
+    }
+
+    /// Möbius function
+    ///
+    /// Out: 0 if N is not squarefree, otherwise (-1)^k where k is the number of prime factors
+    pub const fn moebius(&self) -> i8 {
+        if !self.is_squarefree() {
+            return 0;
+        }
+        if self.len() & 1 == 0 {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Liouville's function
+    ///
+    /// Out: (-1)^k where k is the number of prime factors counted with multiplicity, 0 for N = 0
+    pub const fn liouville(&self) -> i8 {
+        if self.n() == 0 {
+            return 0;
+        }
+        let mut omega = 0;
+        let mut idx = 0;
+        while let Some((_, e)) = self.get(idx) {
+            omega += e;
+            idx += 1;
+        }
+        if omega & 1 == 0 {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Product of the distinct primes dividing N
+    pub const fn radical(&self) -> u64 {
+        if self.n() == 0 {
+            return 0;
+        }
+        let mut rad = 1;
+        let mut idx = 0;
+        while let Some((p, _)) = self.get(idx) {
+            rad *= p;
+            idx += 1;
+        }
+        rad
+    }
+
+    /// Check if no square greater than 1 divides N
+    pub const fn is_squarefree(&self) -> bool {
+        if self.n() == 0 {
+            return false;
+        }
+        let mut idx = 0;
+        while let Some((_, e)) = self.get(idx) {
+            if e > 1 {
+                return false;
+            }
+            idx += 1;
+        }
+        true
+    }
+
+    /// Iterator over the divisors of N, in no particular order
+    pub const fn divisors(&self) -> Divisors {
+        let mut prime = [0u64; FACTOR_CAPACITY];
+        let mut exponent = [0u32; FACTOR_CAPACITY];
+        let mut idx = 0;
+        while let Some((p, e)) = self.get(idx) {
+            prime[idx] = p;
+            exponent[idx] = e;
+            idx += 1;
+        }
+        Divisors {
+            prime,
+            exponent,
+            len: self.len(),
+            current: [0; FACTOR_CAPACITY],
+            divisor: 1,
+            remaining: self.divisor_count(),
+        }
+    }
+}
+
+/// Iterator over the divisors of a 64-bit integer
+#[derive(Clone, Debug)]
+pub struct Divisors {
+    prime: [u64; FACTOR_CAPACITY],
+    exponent: [u32; FACTOR_CAPACITY],
+    len: usize,
+    // Exponents of the next divisor
+    current: [u32; FACTOR_CAPACITY],
+    divisor: u64,
+    remaining: u64,
+}
+
+impl Iterator for Divisors {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let d = self.divisor;
+        // Increment the mixed-radix counter, resetting exponents that are at their maximum
+        for idx in 0..self.len {
+            if self.current[idx] < self.exponent[idx] {
+                self.current[idx] += 1;
+                self.divisor *= self.prime[idx];
+                break;
+            }
+            self.divisor /= self.prime[idx].pow(self.current[idx]);
+            self.current[idx] = 0;
+        }
+        Some(d)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining) {
+            Ok(r) => (r, Some(r)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl FusedIterator for Divisors {}
+
+/// Euler's totient
+///
+/// In: N
+///
+/// Out: Number of integers in [1,N] coprime to N
+pub const fn totient(n: u64) -> u64 {
+    factor(n).totient()
+}
+
+/// Carmichael's function
+///
+/// In: N
+///
+/// Out: Least m such that a^m ≡ 1 (mod N) for every a coprime to N
+pub const fn carmichael(n: u64) -> u64 {
+    factor(n).carmichael()
+}
+
+/// Sum of the k-th powers of the divisors
+///
+/// In: N, K
+///
+/// Out: sigma_k(N), None if it overflows u128
+pub const fn sigma(n: u64, k: u32) -> Option<u128> {
+    factor(n).sigma(k)
+}
+
+/// Number of divisors
+pub const fn divisor_count(n: u64) -> u64 {
+    factor(n).divisor_count()
+}
+
+/// Möbius function
+///
+/// In: N
+///
+/// Out: 0 if N is not squarefree, otherwise (-1)^k where k is the number of prime factors
+pub const fn moebius(n: u64) -> i8 {
+    factor(n).moebius()
+}
+
+/// Liouville's function
+///
+/// In: N
+///
+/// Out: (-1)^k where k is the number of prime factors counted with multiplicity
+pub const fn liouville(n: u64) -> i8 {
+    factor(n).liouville()
+}
+
+/// Product of the distinct primes dividing N
+pub const fn radical(n: u64) -> u64 {
+    factor(n).radical()
+}
+
+/// Check if no square greater than 1 divides N
+pub const fn is_squarefree(n: u64) -> bool {
+    factor(n).is_squarefree()
+}
+
+/// Iterator over the divisors of N, in no particular order
+pub const fn divisors(n: u64) -> Divisors {
+    factor(n).divisors()
+}
+
+// In: A, B > 0 with lcm(A,B) < 2^128
+// Out: lcm(A,B)
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn lcm_128(a: u128, b: u128) -> u128 {
+    a / gcd_128(a, b) * b
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl Factorization128 {
+    /// Euler's totient, the number of integers in [1,N] coprime to N
+    pub const fn totient(&self) -> u128 {
+        if self.n() == 0 {
+            return 0;
+        }
+        let mut phi = self.n();
+        let mut idx = 0;
+        while let Some((p, _)) = self.get(idx) {
+            phi = phi / p * (p - 1);
+            idx += 1;
+        }
+        phi
+    }
+
+    /// Carmichael's function, the exponent of the multiplicative group modulo N
+    pub const fn carmichael(&self) -> u128 {
+        if self.n() == 0 {
+            return 0;
+        }
+        let mut lambda = 1;
+        let mut idx = 0;
+        while let Some((p, e)) = self.get(idx) {
+            let l = if p == 2 {
+                if e < 3 {
+                    1 << (e - 1)
+                } else {
+                    1 << (e - 2)
+                }
+            } else {
+                p.pow(e - 1) * (p - 1)
+            };
+            lambda = lcm_128(lambda, l);
+            idx += 1;
+        }
+        lambda
+    }
+
+    /// Sum of the k-th powers of the divisors
+    ///
+    /// Out: sigma_k(N), None if it overflows u128
+    pub const fn sigma(&self, k: u32) -> Option<u128> {
+        if self.n() == 0 {
+            return Some(0);
+        }
+        let mut sum = 1u128;
+        let mut idx = 0;
+        while let Some((p, e)) = self.get(idx) {
+            let term = match geometric_sum(p, k, e) {
+                Some(t) => t,
+                None => return None,
+            };
+            sum = match sum.checked_mul(term) {
+                Some(s) => s,
+                None => return None,
+            };
+            idx += 1;
+        }
+        Some(sum)
+    }
+
+    /// Number of divisors
+    pub const fn divisor_count(&self) -> u64 {
+        if self.n() == 0 {
+            return 0;
+        }
+        let mut count = 1;
+        let mut idx = 0;
+        while let Some((_, e)) = self.get(idx) {
+            count *= e as u64 + 1;
+            idx += 1;
+        }
+        count
+    }
+
+    /// Möbius function
+    ///
+    /// Out: 0 if N is not squarefree, otherwise (-1)^k where k is the number of prime factors
+    pub const fn moebius(&self) -> i8 {
+        if !self.is_squarefree() {
+            return 0;
+        }
+        if self.len() & 1 == 0 {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Liouville's function
+    ///
+    /// Out: (-1)^k where k is the number of prime factors counted with multiplicity, 0 for N = 0
+    pub const fn liouville(&self) -> i8 {
+        if self.n() == 0 {
+            return 0;
+        }
+        let mut omega = 0;
+        let mut idx = 0;
+        while let Some((_, e)) = self.get(idx) {
+            omega += e;
+            idx += 1;
+        }
+        if omega & 1 == 0 {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Product of the distinct primes dividing N
+    pub const fn radical(&self) -> u128 {
+        if self.n() == 0 {
+            return 0;
+        }
+        let mut rad = 1;
+        let mut idx = 0;
+        while let Some((p, _)) = self.get(idx) {
+            rad *= p;
+            idx += 1;
+        }
+        rad
+    }
+
+    /// Check if no square greater than 1 divides N
+    pub const fn is_squarefree(&self) -> bool {
+        if self.n() == 0 {
+            return false;
+        }
+        let mut idx = 0;
+        while let Some((_, e)) = self.get(idx) {
+            if e > 1 {
+                return false;
+            }
+            idx += 1;
+        }
+        true
+    }
+
+    /// Iterator over the divisors of N, in no particular order
+    pub const fn divisors(&self) -> Divisors128 {
+        let mut prime = [0u128; FACTOR_CAPACITY_128];
+        let mut exponent = [0u32; FACTOR_CAPACITY_128];
+        let mut idx = 0;
+        while let Some((p, e)) = self.get(idx) {
+            prime[idx] = p;
+            exponent[idx] = e;
+            idx += 1;
+        }
+        Divisors128 {
+            prime,
+            exponent,
+            len: self.len(),
+            current: [0; FACTOR_CAPACITY_128],
+            divisor: 1,
+            remaining: self.divisor_count(),
+        }
+    }
+}
+
+/// Iterator over the divisors of a 128-bit integer
+#[cfg(any(feature = "wide", feature = "qft"))]
+#[derive(Clone, Debug)]
+pub struct Divisors128 {
+    prime: [u128; FACTOR_CAPACITY_128],
+    exponent: [u32; FACTOR_CAPACITY_128],
+    len: usize,
+    current: [u32; FACTOR_CAPACITY_128],
+    divisor: u128,
+    remaining: u64,
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl Iterator for Divisors128 {
+    type Item = u128;
+
+    fn next(&mut self) -> Option<u128> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let d = self.divisor;
+        for idx in 0..self.len {
+            if self.current[idx] < self.exponent[idx] {
+                self.current[idx] += 1;
+                self.divisor *= self.prime[idx];
+                break;
+            }
+            self.divisor /= self.prime[idx].pow(self.current[idx]);
+            self.current[idx] = 0;
+        }
+        Some(d)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining) {
+            Ok(r) => (r, Some(r)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl FusedIterator for Divisors128 {}
+
+/// Euler's totient, 128-bit form
+///
+/// In: N
+///
+/// Out: Number of integers in [1,N] coprime to N
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn totient_128(n: u128) -> u128 {
+    factor_128(n).totient()
+}
+
+/// Carmichael's function, 128-bit form
+///
+/// In: N
+///
+/// Out: Least m such that a^m ≡ 1 (mod N) for every a coprime to N
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn carmichael_128(n: u128) -> u128 {
+    factor_128(n).carmichael()
+}
+
+/// Sum of the k-th powers of the divisors, 128-bit form
+///
+/// In: N, K
+///
+/// Out: sigma_k(N), None if it overflows u128
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn sigma_128(n: u128, k: u32) -> Option<u128> {
+    factor_128(n).sigma(k)
+}
+
+/// Number of divisors, 128-bit form
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn divisor_count_128(n: u128) -> u64 {
+    factor_128(n).divisor_count()
+}
+
+/// Möbius function, 128-bit form
+///
+/// In: N
+///
+/// Out: 0 if N is not squarefree, otherwise (-1)^k where k is the number of prime factors
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn moebius_128(n: u128) -> i8 {
+    factor_128(n).moebius()
+}
+
+/// Liouville's function, 128-bit form
+///
+/// In: N
+///
+/// Out: (-1)^k where k is the number of prime factors counted with multiplicity
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn liouville_128(n: u128) -> i8 {
+    factor_128(n).liouville()
+}
+
+/// Product of the distinct primes dividing N, 128-bit form
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn radical_128(n: u128) -> u128 {
+    factor_128(n).radical()
+}
+
+/// Check if no square greater than 1 divides N, 128-bit form
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn is_squarefree_128(n: u128) -> bool {
+    factor_128(n).is_squarefree()
+}
+
+/// Iterator over the divisors of N in no particular order, 128-bit form
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn divisors_128(n: u128) -> Divisors128 {
+    factor_128(n).divisors()
+}
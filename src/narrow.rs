@@ -0,0 +1,230 @@
+#[cfg(any(feature = "lucas", feature = "ssmr"))]
+use crate::primes::{INV_8, PRIME_TABLE_32};
+
+#[cfg(feature = "ssmr")]
+use crate::hashbase::FERMAT_WITNESS_32;
+
+/// Multiplicative inverse over Z/2^32
+///
+///  In:  n \in 2Z + 1
+///
+/// Out: n^-1
+pub const fn mul_inv2_32(n: u32) -> u32 {
+    #[cfg(not(any(feature = "lucas", feature = "ssmr")))]
+    {
+        let mut est: u32 = 3u32.wrapping_mul(n) ^ 2;
+        est = 2u32.wrapping_sub(est.wrapping_mul(n)).wrapping_mul(est);
+        est = 2u32.wrapping_sub(est.wrapping_mul(n)).wrapping_mul(est);
+        est = 2u32.wrapping_sub(est.wrapping_mul(n)).wrapping_mul(est);
+        est
+    }
+
+    #[cfg(any(feature = "lucas", feature = "ssmr"))]
+    {
+        let mut est: u32 = INV_8[(n.wrapping_shr(1) & 0x7F) as usize] as u32;
+        est = 2u32.wrapping_sub(est.wrapping_mul(n)).wrapping_mul(est);
+        est = 2u32.wrapping_sub(est.wrapping_mul(n)).wrapping_mul(est);
+        est
+    }
+}
+
+/// Subtraction in Montgomery form, 32-bit form
+///
+/// In: X,Y,N
+///
+/// Out: X-Y mod N
+pub const fn mont_sub_32(x: u32, y: u32, n: u32) -> u32 {
+    if x >= y {
+        x - y
+    } else {
+        x.wrapping_sub(y).wrapping_add(n)
+    }
+}
+
+/// Product in Montgomery form, 32-bit form
+///
+/// In: Mont(X,N),Mont(Y,N), N^-1,N
+///
+/// Out: Mont(X*Y,N)
+pub const fn mont_prod_32(x: u32, y: u32, inv: u32, n: u32) -> u32 {
+    let prod = x as u64 * y as u64;
+    let (lo, hi) = (prod as u32, (prod >> 32) as u32);
+    // Then find a multiple of n with the same low-order word
+    let borrow = ((lo.wrapping_mul(inv) as u64 * n as u64) >> 32) as u32;
+    mont_sub_32(hi, borrow, n)
+}
+
+/// Convert to Montgomery form, 32-bit form
+///
+/// In: X, N
+///
+/// Out: Mont(X,N)
+#[inline]
+pub const fn to_mont_32(x: u32, n: u32) -> u32 {
+    (((x as u64) << 32) % (n as u64)) as u32
+}
+
+/// One in Montgomery form, 32-bit form
+///
+/// In: N
+///
+/// Out: Mont(1,N)
+#[inline]
+pub const fn one_mont_32(n: u32) -> u32 {
+    n.wrapping_neg() % n
+}
+
+/// Two in Montgomery form, 32-bit form
+///
+/// In: Mont(1,N), N
+///
+/// Out: Mont(2,N)
+pub const fn two_mont_32(one: u32, n: u32) -> u32 {
+    let two = 2 * one; // Can't overflow due to way one is computed
+    if two >= n {
+        return two - n;
+    }
+    two
+}
+
+/// Modular exponentiation in Montgomery form, 32-bit form
+///
+///  In: Mont(base),Mont(1),pow, inv,n
+///
+/// Out: base^pow mod n
+pub const fn mont_pow_32(mut base: u32, mut one: u32, mut pow: u32, inv: u32, n: u32) -> u32 {
+    while pow > 1 {
+        if pow & 1 == 0 {
+            base = mont_prod_32(base, base, inv, n);
+            pow >>= 1;
+        } else {
+            one = mont_prod_32(one, base, inv, n);
+            base = mont_prod_32(base, base, inv, n);
+            pow >>= 1;
+        }
+    }
+    mont_prod_32(one, base, inv, n)
+}
+
+/// Fermat witness selection for n < 2^32
+#[cfg(feature = "ssmr")]
+#[inline]
+pub const fn witness_selector_32(x: u32) -> u32 {
+    FERMAT_WITNESS_32[(x.wrapping_mul(0x9E3779B1) >> 20) as usize] as u32
+}
+
+/// Strong Fermat test, 32-bit form
+///
+/// In: N,tz := a*2^tz+1 =N, Mont(base,N), Mont(1,N), Mont(N-1,N), N^-1
+///
+/// Out: SPRP(N,base)
+pub const fn strong_fermat_32(n: u32, tz: u32, base: u32, one: u32, oneinv: u32, inv: u32) -> bool {
+    let d = n >> tz;
+
+    let mut result = mont_pow_32(base, one, d, inv, n);
+
+    if result == one || result == oneinv {
+        return true;
+    }
+
+    let mut count = 1;
+
+    while count < tz {
+        count += 1;
+
+        result = mont_prod_32(result, result, inv, n);
+
+        if result == oneinv {
+            return true;
+        }
+    }
+    false
+}
+
+const fn core_primality_32(x: u32) -> bool {
+    let inv = mul_inv2_32(x);
+
+    let tzc = (x - 1).trailing_zeros();
+
+    let one = one_mont_32(x);
+
+    let oneinv = x.wrapping_sub(one);
+
+    #[cfg(feature = "ssmr")]
+    {
+        // Every odd integer less than 2^32 is decided by the single witness in its bucket
+        let wit = witness_selector_32(x);
+        // Most buckets use base 2, which avoids the division
+        let base = if wit == 2 { two_mont_32(one, x) } else { to_mont_32(wit, x) };
+
+        strong_fermat_32(x, tzc, base, one, oneinv, inv)
+    }
+    #[cfg(not(feature = "ssmr"))]
+    {
+        // Jaeschke's witnesses {2,7,61} are sufficient for n < 4759123141
+        if !strong_fermat_32(x, tzc, two_mont_32(one, x), one, oneinv, inv) {
+            return false;
+        }
+        // The witnesses are multiples of these primes
+        if x == 7 || x == 61 {
+            return true;
+        }
+
+        strong_fermat_32(x, tzc, to_mont_32(7, x), one, oneinv, inv)
+            && strong_fermat_32(x, tzc, to_mont_32(61, x), one, oneinv, inv)
+    }
+}
+
+/// 32-bit is_prime
+///
+/// Uses trial division by the first 129 primes if Lucas or SSMR is enabled.
+#[no_mangle]
+pub const extern "C" fn is_prime_32(x: u32) -> bool {
+    if x == 1 {
+        return false;
+    }
+
+    if x == 2 {
+        return true;
+    }
+
+    if x & 1 == 0 {
+        return false;
+    }
+
+    #[cfg(any(feature = "lucas", feature = "ssmr"))]
+    {
+        let mut idx: usize = 0;
+
+        while idx < 256 {
+            // Multiply x by a prime inverse over 2^32
+            let prod = x.wrapping_mul(PRIME_TABLE_32[idx]);
+
+            if prod <= PRIME_TABLE_32[idx + 1] {
+                return prod == 1;
+            }
+
+            idx += 2;
+        }
+    } // end conditional block
+
+    core_primality_32(x)
+}
+
+/// 32-bit is_prime_wc
+///
+/// Panics at zero, flags 1 as prime, 2 as composite.
+/// # SSMR
+/// A single strong fermat test with a hashed witness, no additional errors for odd integers
+/// # Lucas/Tiny
+/// Strong fermat test to the bases 2,7 and 61, no additional errors
+#[no_mangle]
+pub const extern "C" fn is_prime_wc_32(x: u32) -> bool {
+    debug_assert!(x != 1 && x != 2 && x != 0);
+    #[cfg(feature = "ssmr")]
+    {
+        debug_assert!(x & 1 == 1);
+    }
+
+    core_primality_32(x)
+}
@@ -0,0 +1,256 @@
+use crate::check::{is_prime, mont_pow, mul_inv2, one_mont, to_mont};
+use crate::factor::{factor, gcd, Factorization};
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::factor_128::{factor_128, gcd_128, Factorization128};
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wide::{is_prime_128, mont_pow_128, mul_inv2_128, one_mont_128, to_mont_128};
+
+/*
+   Multiplicative order and primitive roots
+
+   For N = 2^s m with m odd, the order of a modulo N is the lcm of its orders modulo 2^s and modulo m. Modulo 2^s
+   the order is a power of 2, found by squaring over Z/2^64. Modulo m the order divides λ(m), it is found by removing
+   each prime q from λ(m) while a^(λ/q) ≡ 1, with exponentiation in Montgomery form.
+
+   g is a primitive root of a prime p if g^((p-1)/q) ≢ 1 (mod p) for every prime q dividing p-1. The least primitive
+   root is found by testing g = 2, 3, ... in turn, which takes few attempts as the least primitive root is small in practice.
+*/
+
+// In: A odd, S < 64
+// Out: Order of A modulo 2^S
+const fn order_pow2(a: u64, s: u32) -> u64 {
+    let mask = (1u64 << s) - 1;
+    let mut x = a & mask;
+    let mut order = 1;
+    while x != 1 & mask {
+        x = x.wrapping_mul(x) & mask;
+        order <<= 1;
+    }
+    order
+}
+
+// In: A < M coprime to M, M odd, M > 1
+// Out: Order of A modulo M
+const fn order_odd(a: u64, m: u64) -> u64 {
+    let inv = mul_inv2(m);
+    let one = one_mont(m);
+    let base = to_mont(a, m);
+    let lambda = factor(m).carmichael();
+    let factors = factor(lambda);
+
+    let mut order = lambda;
+    let mut idx = 0;
+    while let Some((q, _)) = factors.get(idx) {
+        while order.is_multiple_of(q) && mont_pow(base, one, order / q, inv, m) == one {
+            order /= q;
+        }
+        idx += 1;
+    }
+    order
+}
+
+/// Multiplicative order
+///
+/// In: A, N
+///
+/// Out: Least k > 0 such that A^k ≡ 1 (mod N), None if N = 0 or gcd(A,N) != 1
+pub const fn multiplicative_order(a: u64, n: u64) -> Option<u64> {
+    if n == 0 || gcd(a, n) != 1 {
+        return None;
+    }
+    let s = n.trailing_zeros();
+    let m = n >> s;
+    let even = order_pow2(a, s);
+    if m == 1 {
+        return Some(even);
+    }
+    let odd = order_odd(a % m, m);
+    Some(odd / gcd(odd, even) * even)
+}
+
+// In: P odd prime, factorisation of P-1
+// Out: True if G has order P-1
+pub(crate) const fn has_full_order(g: u64, p: u64, factors: &Factorization) -> bool {
+    let inv = mul_inv2(p);
+    let one = one_mont(p);
+    let base = to_mont(g % p, p);
+    if base == 0 {
+        return false;
+    }
+    let mut idx = 0;
+    while let Some((q, _)) = factors.get(idx) {
+        if mont_pow(base, one, (p - 1) / q, inv, p) == one {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
+
+// In: P odd prime, factorisation of P-1
+// Out: Least primitive root of P
+pub(crate) const fn least_primitive_root(p: u64, factors: &Factorization) -> u64 {
+    let mut g = 2;
+    while !has_full_order(g, p, factors) {
+        g += 1;
+    }
+    g
+}
+
+/// Least primitive root
+///
+/// In: P
+///
+/// Out: Least g > 0 that generates the multiplicative group modulo P, None if P is not prime
+pub const fn primitive_root(p: u64) -> Option<u64> {
+    if !is_prime(p) {
+        return None;
+    }
+    if p == 2 {
+        return Some(1);
+    }
+    Some(least_primitive_root(p, &factor(p - 1)))
+}
+
+/// Primitive root check
+///
+/// In: G, P
+///
+/// Out: True if P is prime and G generates the multiplicative group modulo P
+pub const fn is_primitive_root(g: u64, p: u64) -> bool {
+    if !is_prime(p) {
+        return false;
+    }
+    if p == 2 {
+        return g & 1 == 1;
+    }
+    has_full_order(g, p, &factor(p - 1))
+}
+
+// In: A odd, S < 128
+// Out: Order of A modulo 2^S
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn order_pow2_128(a: u128, s: u32) -> u128 {
+    let mask = (1u128 << s) - 1;
+    let mut x = a & mask;
+    let mut order = 1;
+    while x != 1 & mask {
+        x = x.wrapping_mul(x) & mask;
+        order <<= 1;
+    }
+    order
+}
+
+// In: A < M coprime to M, M odd, M > 1
+// Out: Order of A modulo M
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn order_odd_128(a: u128, m: u128) -> u128 {
+    let inv = mul_inv2_128(m);
+    let one = one_mont_128(m);
+    let base = to_mont_128(a, m);
+    let lambda = factor_128(m).carmichael();
+    let factors = factor_128(lambda);
+
+    let mut order = lambda;
+    let mut idx = 0;
+    while let Some((q, _)) = factors.get(idx) {
+        while order.is_multiple_of(q) && mont_pow_128(base, one, order / q, inv, m) == one {
+            order /= q;
+        }
+        idx += 1;
+    }
+    order
+}
+
+/// Multiplicative order, 128-bit form
+///
+/// In: A, N
+///
+/// Out: Least k > 0 such that A^k ≡ 1 (mod N), None if N = 0 or gcd(A,N) != 1
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn multiplicative_order_128(a: u128, n: u128) -> Option<u128> {
+    if n == 0 {
+        return None;
+    }
+    if n <= u64::MAX as u128 {
+        return match multiplicative_order((a % n) as u64, n as u64) {
+            Some(order) => Some(order as u128),
+            None => None,
+        };
+    }
+    if gcd_128(a, n) != 1 {
+        return None;
+    }
+    let s = n.trailing_zeros();
+    let m = n >> s;
+    let even = order_pow2_128(a, s);
+    if m == 1 {
+        return Some(even);
+    }
+    let odd = order_odd_128(a % m, m);
+    Some(odd / gcd_128(odd, even) * even)
+}
+
+// In: P odd prime, factorisation of P-1
+// Out: True if G has order P-1
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn has_full_order_128(g: u128, p: u128, factors: &Factorization128) -> bool {
+    let inv = mul_inv2_128(p);
+    let one = one_mont_128(p);
+    let base = to_mont_128(g % p, p);
+    if base == 0 {
+        return false;
+    }
+    let mut idx = 0;
+    while let Some((q, _)) = factors.get(idx) {
+        if mont_pow_128(base, one, (p - 1) / q, inv, p) == one {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
+
+/// Least primitive root, 128-bit form
+///
+/// In: P
+///
+/// Out: Least g > 0 that generates the multiplicative group modulo P, None if P is not prime
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn primitive_root_128(p: u128) -> Option<u128> {
+    if p <= u64::MAX as u128 {
+        return match primitive_root(p as u64) {
+            Some(g) => Some(g as u128),
+            None => None,
+        };
+    }
+    if !is_prime_128(p) {
+        return None;
+    }
+    let factors = factor_128(p - 1);
+    let mut g = 2;
+    while !has_full_order_128(g, p, &factors) {
+        g += 1;
+    }
+    Some(g)
+}
+
+/// Primitive root check, 128-bit form
+///
+/// In: G, P
+///
+/// Out: True if P is prime and G generates the multiplicative group modulo P
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn is_primitive_root_128(g: u128, p: u128) -> bool {
+    if p < 2 {
+        return false;
+    }
+    if p <= u64::MAX as u128 {
+        return is_primitive_root((g % p) as u64, p as u64);
+    }
+    if !is_prime_128(p) {
+        return false;
+    }
+    has_full_order_128(g, p, &factor_128(p - 1))
+}
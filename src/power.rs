@@ -0,0 +1,261 @@
+use crate::check::is_prime;
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wide::is_prime_128;
+
+/*
+   Perfect powers and integer roots
+
+   N = b^k for some k > 1 if and only if N is a p-th power for some prime p, so only prime exponents up to log2(N) are
+   tried. If the exponent found is p, the root is checked again to find the greatest exponent.
+
+   Most candidates are rejected before a root is computed. If N is even then p must divide the number of trailing zeros.
+   Otherwise N must be a p-th power residue modulo small q with q ≡ 1 (mod p), where only about 1/p of the residues are
+   p-th powers. For squares the moduli 64, 63, 65 and 11 together pass about 1 in 120 non-squares.
+
+   Roots are computed by Newton's iteration from an initial estimate above the root, which then decreases monotonically
+   to the floor of the root. Square roots use isqrt.
+*/
+
+// Out: Bit r is set if r ≡ x^k (mod q) for some x
+const fn power_residues(k: u32, q: u64) -> u128 {
+    let mut mask = 0u128;
+    let mut x = 0;
+    while x < q {
+        mask |= 1 << ((x as u128).pow(k) % q as u128);
+        x += 1;
+    }
+    mask
+}
+
+const fn filter(k: u32, q: u64) -> (u32, u64, u128) {
+    (k, q, power_residues(k, q))
+}
+
+/// Exponent, modulus and power residues modulo q
+const POWER_FILTER: [(u32, u64, u128); 16] = [
+    filter(2, 64),
+    filter(2, 63),
+    filter(2, 65),
+    filter(2, 11),
+    filter(3, 63),
+    filter(3, 13),
+    filter(3, 19),
+    filter(5, 11),
+    filter(5, 31),
+    filter(7, 29),
+    filter(7, 43),
+    filter(11, 23),
+    filter(11, 67),
+    filter(13, 53),
+    filter(13, 79),
+    filter(17, 103),
+];
+
+/// Prime exponents that a 128-bit perfect power may have
+const EXPONENTS: [u32; 31] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97, 101, 103, 107, 109,
+    113, 127,
+];
+
+// In: N, residues of N modulo each filter modulus, prime K
+// Out: False if N is certainly not a K-th power
+const fn passes_filter(residues: &[u64; POWER_FILTER.len()], k: u32) -> bool {
+    let mut idx = 0;
+    while idx < POWER_FILTER.len() {
+        let (exp, _, mask) = POWER_FILTER[idx];
+        if exp == k && (mask >> residues[idx]) & 1 == 0 {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
+
+// In: floor(log2(N)), trailing zeros of N
+// Out: Upper bound on the exponent of a perfect power N, the base of an odd power is at least 3
+const fn max_exponent(log2: u32, tz: u32) -> u32 {
+    if tz == 0 {
+        // log3(N) < (floor(log2(N))+1) * 0.631
+        return (log2 + 1) * 631 / 1000;
+    }
+    log2
+}
+
+/// Integer k-th root
+///
+/// In: N, K > 0
+///
+/// Out: floor(N^(1/K)), panics if K = 0
+pub const fn iroot(n: u64, k: u32) -> u64 {
+    assert!(k > 0, "zeroth root is undefined");
+    if k == 1 || n < 2 {
+        return n;
+    }
+    if k == 2 {
+        return n.isqrt();
+    }
+    if k >= u64::BITS || n >> k == 0 {
+        return 1;
+    }
+    // 2^(floor(log2(N)/K)+1) exceeds the root
+    let mut x: u64 = 1 << (n.ilog2() / k + 1);
+    loop {
+        let quotient = match x.checked_pow(k - 1) {
+            Some(pow) => n / pow,
+            None => 0,
+        };
+        let y = ((k - 1) as u64 * x + quotient) / k as u64;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Integer k-th root, 128-bit form
+///
+/// In: N, K > 0
+///
+/// Out: floor(N^(1/K)), panics if K = 0
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn iroot_128(n: u128, k: u32) -> u128 {
+    assert!(k > 0, "zeroth root is undefined");
+    if k == 1 || n < 2 {
+        return n;
+    }
+    if k == 2 {
+        return n.isqrt();
+    }
+    if k >= u128::BITS || n >> k == 0 {
+        return 1;
+    }
+    if n <= u64::MAX as u128 {
+        return iroot(n as u64, k) as u128;
+    }
+    let mut x: u128 = 1 << (n.ilog2() / k + 1);
+    loop {
+        let quotient = match x.checked_pow(k - 1) {
+            Some(pow) => n / pow,
+            None => 0,
+        };
+        let y = ((k - 1) as u128 * x + quotient) / k as u128;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Perfect power detection
+///
+/// In: N
+///
+/// Out: (b, k) where N = b^k with k > 1 as large as possible, None if N is not a perfect power or N < 2
+pub const fn is_perfect_power(n: u64) -> Option<(u64, u32)> {
+    if n < 4 {
+        return None;
+    }
+    let tz = n.trailing_zeros();
+    let mut residues = [0u64; POWER_FILTER.len()];
+    let mut idx = 0;
+    while idx < POWER_FILTER.len() {
+        residues[idx] = n % POWER_FILTER[idx].1;
+        idx += 1;
+    }
+
+    let bits = max_exponent(n.ilog2(), tz);
+    let mut idx = 0;
+    while idx < EXPONENTS.len() && EXPONENTS[idx] <= bits {
+        let k = EXPONENTS[idx];
+        idx += 1;
+        if !tz.is_multiple_of(k) || !passes_filter(&residues, k) {
+            continue;
+        }
+        let r = iroot(n, k);
+        if r.pow(k) == n {
+            return match is_perfect_power(r) {
+                Some((b, e)) => Some((b, e * k)),
+                None => Some((r, k)),
+            };
+        }
+    }
+    None
+}
+
+/// Perfect power detection, 128-bit form
+///
+/// In: N
+///
+/// Out: (b, k) where N = b^k with k > 1 as large as possible, None if N is not a perfect power or N < 2
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn is_perfect_power_128(n: u128) -> Option<(u128, u32)> {
+    if n <= u64::MAX as u128 {
+        return match is_perfect_power(n as u64) {
+            Some((b, k)) => Some((b as u128, k)),
+            None => None,
+        };
+    }
+    let tz = n.trailing_zeros();
+    let mut residues = [0u64; POWER_FILTER.len()];
+    let mut idx = 0;
+    while idx < POWER_FILTER.len() {
+        residues[idx] = (n % POWER_FILTER[idx].1 as u128) as u64;
+        idx += 1;
+    }
+
+    let bits = max_exponent(n.ilog2(), tz);
+    let mut idx = 0;
+    while idx < EXPONENTS.len() && EXPONENTS[idx] <= bits {
+        let k = EXPONENTS[idx];
+        idx += 1;
+        if !tz.is_multiple_of(k) || !passes_filter(&residues, k) {
+            continue;
+        }
+        let r = iroot_128(n, k);
+        if r.pow(k) == n {
+            return match is_perfect_power_128(r) {
+                Some((b, e)) => Some((b, e * k)),
+                None => Some((r, k)),
+            };
+        }
+    }
+    None
+}
+
+/// Prime power detection
+///
+/// In: N
+///
+/// Out: (p, k) where N = p^k with p prime and k > 0, None otherwise
+pub const fn is_prime_power(n: u64) -> Option<(u64, u32)> {
+    if let Some((b, k)) = is_perfect_power(n) {
+        if is_prime(b) {
+            return Some((b, k));
+        }
+        return None;
+    }
+    if is_prime(n) {
+        return Some((n, 1));
+    }
+    None
+}
+
+/// Prime power detection, 128-bit form
+///
+/// In: N
+///
+/// Out: (p, k) where N = p^k with p prime and k > 0, None otherwise
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn is_prime_power_128(n: u128) -> Option<(u128, u32)> {
+    if let Some((b, k)) = is_perfect_power_128(n) {
+        if is_prime_128(b) {
+            return Some((b, k));
+        }
+        return None;
+    }
+    if is_prime_128(n) {
+        return Some((n, 1));
+    }
+    None
+}
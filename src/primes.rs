@@ -101,6 +101,48 @@ pub static PRIME_TABLE : [u64;256] = [
 	0x6C3D3D98BED7C42F ,   0x5B2618EC6AD0A5   ,   0x30981EFCD4B010E7 ,   0x5A2553748E42E7 
 ];
 
+// Same table as above except for 2^32
+#[rustfmt::skip]
+#[cfg(any(feature="lucas",feature="ssmr"))]
+#[no_mangle]
+pub static PRIME_TABLE_32 : [u32;256] = [
+
+//  3^-1 mod 2^32   2^32/3         5^-1 mod 2^32   2^32/5
+	0xAAAAAAAB ,   0x55555555 ,   0xCCCCCCCD ,   0x33333333 ,   0xB6DB6DB7 ,   0x24924924 ,   0xBA2E8BA3 ,   0x1745D174 ,
+	0xC4EC4EC5 ,   0x13B13B13 ,   0xF0F0F0F1 ,   0xF0F0F0F  ,   0x286BCA1B ,   0xD79435E  ,   0xE9BD37A7 ,   0xB21642C  ,
+	0x4F72C235 ,   0x8D3DCB0  ,   0xBDEF7BDF ,   0x8421084  ,   0x914C1BAD ,   0x6EB3E45  ,   0xC18F9C19 ,   0x63E7063  ,
+	0x2FA0BE83 ,   0x5F417D0  ,   0x677D46CF ,   0x572620A  ,   0x8C13521D ,   0x4D4873E  ,   0xA08AD8F3 ,   0x456C797  ,
+	0xC10C9715 ,   0x4325C53  ,   0x7A44C6B  ,   0x3D22635  ,   0xE327A977 ,   0x39B0AD1  ,   0xC7E3F1F9 ,   0x381C0E0  ,
+	0x613716AF ,   0x33D91D2  ,   0x2B2E43DB ,   0x3159721  ,   0xFA3F47E9 ,   0x2E05C0B  ,   0x5F02A3A1 ,   0x2A3A0FD  ,
+	0x7C32B16D ,   0x288DF0C  ,   0xD3431B57 ,   0x27C4597  ,   0x8D28AC43 ,   0x2647C69  ,   0xDA6C0965 ,   0x2593F69  ,
+	0xFDBC091  ,   0x243F6F0  ,   0xEFDFBF7F ,   0x2040810  ,   0xC9484E2B ,   0x1F44659  ,   0x77975B9  ,   0x1DE5D6E  ,
+	0x70586723 ,   0x1D77B65  ,   0x8CE2CABD ,   0x1B7D6C3  ,   0xBF937F27 ,   0x1B20364  ,   0x2C0685B5 ,   0x1A16D3F  ,
+	0x451AB30B ,   0x1920FB4  ,   0xDB35A717 ,   0x1886E5F  ,   0xD516325  ,   0x17AD220  ,   0xD962AE7B ,   0x16E1F76  ,
+	0x10F8ED9D ,   0x16A13CD  ,   0xEE936F3F ,   0x1571ED3  ,   0x90948F41 ,   0x1539094  ,   0x3D137E0D ,   0x14CAB88  ,
+	0xEF46C0F7 ,   0x149539E  ,   0x6E68575B ,   0x13698DF  ,   0xDB43BB1F ,   0x125E227  ,   0x9BA144CB ,   0x120B470  ,
+	0x478BBCED ,   0x11E2EF3  ,   0x1FDCD759 ,   0x1194538  ,   0x437B2E0F ,   0x112358E  ,   0x10FEF011 ,   0x10FEF01  ,
+	0x9A020A33 ,   0x105197F  ,   0xFF00FF01 ,   0xFF00FF   ,   0x70E99CB7 ,   0xF92FB2   ,   0x6205B5C5 ,   0xF3A0D5   ,
+	0xA27ACDEF ,   0xF1D48B   ,   0x25E4463D ,   0xEC9791   ,   0x749CB29  ,   0xE93965   ,   0xC9B97113 ,   0xE79372   ,
+	0x84CE32AD ,   0xDFAC1F   ,   0xC74BE1FB ,   0xD578E9   ,   0xA7198487 ,   0xD2BA08   ,   0x39409D09 ,   0xD16154   ,
+	0x6F71DE15 ,   0xCEBCF8   ,   0xBFCE8063 ,   0xC5FE74   ,   0xF61FE7B1 ,   0xC27806   ,   0x70E046D3 ,   0xBCDD53   ,
+	0xF1545AF5 ,   0xBBC840   ,   0x9A7862A1 ,   0xB9A786   ,   0x2A128A57 ,   0xB68D31   ,   0xB7747D8F ,   0xB2927C   ,
+	0xBB5E06DD ,   0xAFB321   ,   0x12E9B5B3 ,   0xACEB0F   ,   0xEC9DBE7F ,   0xAB1CBD   ,   0xEC41CF4D ,   0xA87917   ,
+	0xAEC02945 ,   0xA513FD   ,   0x8382DF71 ,   0xA36E71   ,   0x84B1C2A9 ,   0xA03C16   ,   0x75EB3A0B ,   0x9C6916   ,
+	0xFA86FE2D ,   0x9BAADE   ,   0x3F8DF54F ,   0x980E41   ,   0x975A751  ,   0x975A75   ,   0xC3EFAC07 ,   0x9548E4   ,
+	0xA8299B73 ,   0x93EFD1   ,   0x9BA70E41 ,   0x91F5BC   ,   0x23D9E879 ,   0x8F67A1   ,   0xC494D305 ,   0x8E2917   ,
+	0xAB67652F ,   0x8D8BE3   ,   0xFB10FE5B ,   0x8C5584   ,   0xBF54FA1F ,   0x88D180   ,   0xB98F81D7 ,   0x869222   ,
+	0xE90F1EC3 ,   0x85797B   ,   0xBED87F3B ,   0x8355AC   ,   0x16E70FC7 ,   0x824A4E   ,   0x9DECE355 ,   0x80C121   ,
+	0x73F62C39 ,   0x7DC9F3   ,   0xAD46F9A3 ,   0x7D4ECE   ,   0x24E8D035 ,   0x79237D   ,   0x2319BD8B ,   0x77CF53   ,
+	0xC7ED9DA5 ,   0x75A8AC   ,   0xFEA2C8FB ,   0x7467AC   ,   0xCE0F4C09 ,   0x732D70   ,   0x544986F3 ,   0x72C62A   ,
+	0x55A10DC1 ,   0x7194A1   ,   0x85E33763 ,   0x6FA549   ,   0xD84886B1 ,   0x6E8419   ,   0x31260967 ,   0x6D68B5   ,
+	0xD1FF25E9 ,   0x6D0B80   ,   0x5B84D99F ,   0x6BF790   ,   0x1335DF6D ,   0x6AE907   ,   0x75D5ADD9 ,   0x6A3799   ,
+	0x3C619A43 ,   0x69DFBD   ,   0x4767747  ,   0x67DC4C   ,   0x663D81   ,   0x663D80   ,   0x671DDC2B ,   0x65EC17   ,
+	0xC1E12337 ,   0x654AC8   ,   0x9CD09045 ,   0x645C85   ,   0x91496B9B ,   0x637299   ,   0xC7D7B8BD ,   0x632591   ,
+	0x9F006161 ,   0x6160FF   ,   0x5E28152D ,   0x60CDB5   ,   0xBFE803   ,   0x5FF401   ,   0x9E907C7B ,   0x5ED79E   ,
+	0x76528895 ,   0x5D7D42   ,   0x1CE2C0D  ,   0x5C6F35   ,   0xBED7C42F ,   0x5B2618   ,   0xD4B010E7 ,   0x5A2553   ,
+
+];
+
 // Same table as above except for 2^128
 #[rustfmt::skip]
 #[cfg(all(any(feature="lucas",feature="ssmr"),any(feature="wide",feature="qft")) )]
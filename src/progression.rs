@@ -0,0 +1,377 @@
+use core::iter::FusedIterator;
+
+use crate::check::is_prime;
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wide::is_prime_128;
+
+/*
+   Primes in arithmetic progressions
+
+   Candidates a + kq are stepped in both directions. Primes dividing q never divide a candidate as gcd(a,q) = 1, the
+   remaining primes under 54 are tracked as residues of the front and back candidates, updated by q mod p at each
+   step, so that candidates with a small factor are discarded with a comparison instead of a call to is_prime.
+   This is most effective for large q, where the candidates are not already restricted by the small factors of q.
+*/
+
+/// Primes used to pre-filter candidates
+const FILTER_PRIMES: [u8; 16] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+
+const FILTER_LEN: usize = FILTER_PRIMES.len();
+
+/// Greatest filter prime, candidates above it that the filter rejects are composite
+const FILTER_MAX: u8 = 53;
+
+// In: A, B
+// Out: gcd(A,B)
+pub(crate) const fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+// In: X, A < Q
+// Out: Least D >= 0 such that X + D ≡ A (mod Q)
+const fn offset_up(x: u128, a: u128, q: u128) -> u128 {
+    let r = x % q;
+    if a >= r {
+        a - r
+    } else {
+        a + (q - r)
+    }
+}
+
+// In: X, A < Q
+// Out: Least D >= 0 such that X - D ≡ A (mod Q)
+const fn offset_down(x: u128, a: u128, q: u128) -> u128 {
+    let r = x % q;
+    if r >= a {
+        r - a
+    } else {
+        r + (q - a)
+    }
+}
+
+// The modulus reduced by each filter prime. Primes dividing the modulus have a step of zero, so the residue of every
+// candidate is the nonzero residue of a and they never reject
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Filter {
+    step: [u8; FILTER_LEN],
+}
+
+impl Filter {
+    const fn new(modulus: u128) -> Self {
+        let mut step = [0; FILTER_LEN];
+        let mut idx = 0;
+        while idx < FILTER_LEN {
+            step[idx] = (modulus % FILTER_PRIMES[idx] as u128) as u8;
+            idx += 1;
+        }
+        Self { step }
+    }
+
+    // Out: Residues of X modulo the filter primes
+    const fn residues(x: u128) -> [u8; FILTER_LEN] {
+        let mut res = [0; FILTER_LEN];
+        let mut idx = 0;
+        while idx < FILTER_LEN {
+            res[idx] = (x % FILTER_PRIMES[idx] as u128) as u8;
+            idx += 1;
+        }
+        res
+    }
+
+    // Out: Residues of X + q from those of X
+    #[inline]
+    fn forward(&self, res: &mut [u8; FILTER_LEN]) {
+        for idx in 0..FILTER_LEN {
+            let r = res[idx] + self.step[idx];
+            res[idx] = if r >= FILTER_PRIMES[idx] { r - FILTER_PRIMES[idx] } else { r };
+        }
+    }
+
+    // Out: Residues of X - q from those of X
+    #[inline]
+    fn backward(&self, res: &mut [u8; FILTER_LEN]) {
+        for idx in 0..FILTER_LEN {
+            let r = res[idx] + (FILTER_PRIMES[idx] - self.step[idx]);
+            res[idx] = if r >= FILTER_PRIMES[idx] { r - FILTER_PRIMES[idx] } else { r };
+        }
+    }
+
+    // Out: True if a candidate with these residues has a small factor
+    #[inline]
+    fn rejects(res: &[u8; FILTER_LEN]) -> bool {
+        res.iter().fold(false, |acc, &r| acc | (r == 0))
+    }
+}
+
+/// Iterator over the primes p ≡ a (mod q) in an interval of 64-bit integers
+///
+/// Primes are yielded in increasing order from the front and decreasing order from the back.
+///
+/// ```ignore
+/// use machine_prime::ResiduePrimes;
+///
+/// // Primes of the form 29130n + 1, candidate factors of base-15 fermat pseudoprimes
+/// const CANDIDATES: Option<ResiduePrimes> = ResiduePrimes::new(1, 29130, 0, 1 << 32);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResiduePrimes {
+    modulus: u64,
+    // Remaining candidates front..=back, none remain if empty is set
+    front: u64,
+    back: u64,
+    empty: bool,
+    filter: Filter,
+    front_res: [u8; FILTER_LEN],
+    back_res: [u8; FILTER_LEN],
+}
+
+impl ResiduePrimes {
+    /// Primes p ≡ residue (mod modulus) in [lo,hi)
+    ///
+    /// Out: None if the modulus is zero or gcd(residue,modulus) != 1
+    pub const fn new(residue: u64, modulus: u64, lo: u64, hi: u64) -> Option<Self> {
+        if modulus == 0 {
+            return None;
+        }
+        let residue = residue % modulus;
+        if gcd(residue as u128, modulus as u128) != 1 {
+            return None;
+        }
+        let filter = Filter::new(modulus as u128);
+        let mut iter = Self {
+            modulus,
+            front: 0,
+            back: 0,
+            empty: true,
+            filter,
+            front_res: [0; FILTER_LEN],
+            back_res: [0; FILTER_LEN],
+        };
+        if hi <= lo {
+            return Some(iter);
+        }
+        let last = hi - 1;
+        let front = match lo.checked_add(offset_up(lo as u128, residue as u128, modulus as u128) as u64) {
+            Some(front) if front <= last => front,
+            _ => return Some(iter),
+        };
+        let back = last - offset_down(last as u128, residue as u128, modulus as u128) as u64;
+        iter.front = front;
+        iter.back = back;
+        iter.empty = false;
+        iter.front_res = Filter::residues(front as u128);
+        iter.back_res = Filter::residues(back as u128);
+        Some(iter)
+    }
+
+    /// Modulus of the progression
+    pub const fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    // Out: Candidate passes the filter
+    #[inline]
+    fn admissible(&self, x: u64, res: &[u8; FILTER_LEN]) -> bool {
+        x <= FILTER_MAX as u64 || !Filter::rejects(res)
+    }
+}
+
+impl Iterator for ResiduePrimes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while !self.empty {
+            let x = self.front;
+            let pass = self.admissible(x, &self.front_res);
+            if x == self.back {
+                self.empty = true;
+            } else {
+                // x < back so this cannot overflow
+                self.front = x + self.modulus;
+                self.filter.forward(&mut self.front_res);
+            }
+            if pass && is_prime(x) {
+                return Some(x);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.empty {
+            return (0, Some(0));
+        }
+        let candidates = (self.back - self.front) / self.modulus + 1;
+        (0, usize::try_from(candidates).ok())
+    }
+
+    fn last(mut self) -> Option<u64> {
+        self.next_back()
+    }
+
+    fn min(mut self) -> Option<u64> {
+        self.next()
+    }
+
+    fn max(mut self) -> Option<u64> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for ResiduePrimes {
+    fn next_back(&mut self) -> Option<u64> {
+        while !self.empty {
+            let x = self.back;
+            let pass = self.admissible(x, &self.back_res);
+            if x == self.front {
+                self.empty = true;
+            } else {
+                self.back = x - self.modulus;
+                self.filter.backward(&mut self.back_res);
+            }
+            if pass && is_prime(x) {
+                return Some(x);
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for ResiduePrimes {}
+
+/// Iterator over the primes p ≡ a (mod q) in an interval of 128-bit integers
+///
+/// Primes are yielded in increasing order from the front and decreasing order from the back.
+#[cfg(any(feature = "wide", feature = "qft"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResiduePrimes128 {
+    modulus: u128,
+    front: u128,
+    back: u128,
+    empty: bool,
+    filter: Filter,
+    front_res: [u8; FILTER_LEN],
+    back_res: [u8; FILTER_LEN],
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl ResiduePrimes128 {
+    /// Primes p ≡ residue (mod modulus) in [lo,hi)
+    ///
+    /// Out: None if the modulus is zero or gcd(residue,modulus) != 1
+    pub const fn new(residue: u128, modulus: u128, lo: u128, hi: u128) -> Option<Self> {
+        if modulus == 0 {
+            return None;
+        }
+        let residue = residue % modulus;
+        if gcd(residue, modulus) != 1 {
+            return None;
+        }
+        let filter = Filter::new(modulus);
+        let mut iter = Self {
+            modulus,
+            front: 0,
+            back: 0,
+            empty: true,
+            filter,
+            front_res: [0; FILTER_LEN],
+            back_res: [0; FILTER_LEN],
+        };
+        if hi <= lo {
+            return Some(iter);
+        }
+        let last = hi - 1;
+        let front = match lo.checked_add(offset_up(lo, residue, modulus)) {
+            Some(front) if front <= last => front,
+            _ => return Some(iter),
+        };
+        let back = last - offset_down(last, residue, modulus);
+        iter.front = front;
+        iter.back = back;
+        iter.empty = false;
+        iter.front_res = Filter::residues(front);
+        iter.back_res = Filter::residues(back);
+        Some(iter)
+    }
+
+    /// Modulus of the progression
+    pub const fn modulus(&self) -> u128 {
+        self.modulus
+    }
+
+    #[inline]
+    fn admissible(&self, x: u128, res: &[u8; FILTER_LEN]) -> bool {
+        x <= FILTER_MAX as u128 || !Filter::rejects(res)
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl Iterator for ResiduePrimes128 {
+    type Item = u128;
+
+    fn next(&mut self) -> Option<u128> {
+        while !self.empty {
+            let x = self.front;
+            let pass = self.admissible(x, &self.front_res);
+            if x == self.back {
+                self.empty = true;
+            } else {
+                self.front = x + self.modulus;
+                self.filter.forward(&mut self.front_res);
+            }
+            if pass && is_prime_128(x) {
+                return Some(x);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.empty {
+            return (0, Some(0));
+        }
+        let candidates = (self.back - self.front) / self.modulus + 1;
+        (0, usize::try_from(candidates).ok())
+    }
+
+    fn last(mut self) -> Option<u128> {
+        self.next_back()
+    }
+
+    fn min(mut self) -> Option<u128> {
+        self.next()
+    }
+
+    fn max(mut self) -> Option<u128> {
+        self.next_back()
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl DoubleEndedIterator for ResiduePrimes128 {
+    fn next_back(&mut self) -> Option<u128> {
+        while !self.empty {
+            let x = self.back;
+            let pass = self.admissible(x, &self.back_res);
+            if x == self.front {
+                self.empty = true;
+            } else {
+                self.back = x - self.modulus;
+                self.filter.backward(&mut self.back_res);
+            }
+            if pass && is_prime_128(x) {
+                return Some(x);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl FusedIterator for ResiduePrimes128 {}
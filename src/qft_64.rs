@@ -0,0 +1,125 @@
+use crate::check::{add_mod, mont_prod, mont_sub, mul_inv2_newton, one_mont, strong_fermat, to_mont, two_mont};
+use crate::symbol::jacobi;
+
+/*
+  Khashin's Quadratic Frobenius test over 64-bit integers
+
+  The same test as qft.rs, let c be the first element of [-1,2,3,5,7,...] with Jacobi(c,n) = -1, then n is a
+  probable prime if (2 + sqrt(-1))^n = 2 - sqrt(-1), (2 + sqrt(2))^n = 2 - sqrt(2) or (1 + sqrt(c))^n = 1 - sqrt(c)
+  respectively, evaluated in Montgomery form with mont_prod.
+
+  is_prime_qft precedes it by a base-2 strong fermat test and uses no tables, so it is independent of the witness
+  tables of SSMR and the parameter table of Lucas. Squares have no c with Jacobi(c,n) = -1 and are removed first.
+*/
+
+// (x + y sqrt(c))(a + b sqrt(c)) where C is Mont(c) and NEG_ONE selects c = -1 without a product
+const fn quad_prod(x: (u64, u64), a: (u64, u64), c: u64, neg_one: bool, inv: u64, n: u64) -> (u64, u64) {
+    let yb = mont_prod(x.1, a.1, inv, n);
+    let xa = mont_prod(x.0, a.0, inv, n);
+    let real = if neg_one {
+        mont_sub(xa, yb, n)
+    } else {
+        add_mod(xa, mont_prod(yb, c, inv, n), n)
+    };
+    let imag = add_mod(mont_prod(x.0, a.1, inv, n), mont_prod(x.1, a.0, inv, n), n);
+    (real, imag)
+}
+
+const fn quad_pow(mut base: (u64, u64), mut one: (u64, u64), c: u64, neg_one: bool, mut pow: u64, inv: u64, n: u64) -> (u64, u64) {
+    while pow > 1 {
+        if pow & 1 == 1 {
+            one = quad_prod(one, base, c, neg_one, inv, n);
+        }
+        base = quad_prod(base, base, c, neg_one, inv, n);
+        pow >>= 1;
+    }
+    quad_prod(one, base, c, neg_one, inv, n)
+}
+
+// In: n \in 2Z+1, not a square
+// Out: The minimum element x in the sequence [-1,2,3,5,7,...] where Jacobi(x,n) = -1
+const fn frobenius_idx(n: u64) -> i32 {
+    if n & 3 == 3 {
+        return -1;
+    }
+    if n & 7 == 5 {
+        return 2;
+    }
+    if n % 12 == 5 || n % 12 == 7 {
+        return 3;
+    }
+    if n % 5 == 2 || n % 5 == 3 {
+        return 5;
+    }
+    let mut idx = 7;
+    while jacobi(idx, n) != -1 {
+        idx += 2;
+    }
+    idx as i32
+}
+
+/// Khashin's Quadratic Frobenius test, 64-bit form
+///
+/// In: N odd and not a square, Mont(1,N), Mont(2,N), Mont(N-1,N), N^-1
+///
+/// Out: QFT(N)
+pub const fn qft_64(n: u64, one: u64, two: u64, oneinv: u64, inv: u64) -> bool {
+    let idx = frobenius_idx(n);
+    let mul_ident = (one, 0);
+    let (base, c) = match idx {
+        // 2 + sqrt(-1) and 2 + sqrt(2)
+        -1 | 2 => ((two, one), two),
+        // 1 + sqrt(c)
+        _ => ((one, one), to_mont(idx as u64 % n, n)),
+    };
+    let residue = quad_pow(base, mul_ident, c, idx == -1, n, inv, n);
+    // Compare to the conjugate
+    residue.0 == base.0 && residue.1 == oneinv
+}
+
+/// Primality testing by a base-2 strong fermat test and Khashin's QFT
+///
+/// Uses no look-up tables and gives the same result in every build, so it can be checked against is_prime.
+/// No counterexamples are known, Khashin evaluated the QFT up to 2^64.
+pub const fn is_prime_qft(x: u64) -> bool {
+    if x < 2 {
+        return false;
+    }
+    if x & 1 == 0 {
+        return x == 2;
+    }
+    let inv = mul_inv2_newton(x);
+    let tzc = (x - 1).trailing_zeros();
+    let one = one_mont(x);
+    let oneinv = x.wrapping_sub(one);
+    let two = two_mont(one, x);
+
+    if !strong_fermat(x, tzc, two, one, oneinv, inv) {
+        return false;
+    }
+    let sqrt = x.isqrt();
+    if sqrt * sqrt == x {
+        return false;
+    }
+    qft_64(x, one, two, oneinv, inv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_prime_qft;
+
+    // Runs in every build, including --no-default-features where mul_inv2 has no INV_8 table
+    #[test]
+    fn qft_table_free() {
+        assert!(is_prime_qft(1000003));
+        assert!(is_prime_qft(18446744073709551557));
+        // Base-2 strong pseudoprimes and the squares of the Wieferich primes
+        assert!(!is_prime_qft(3215031751));
+        assert!(!is_prime_qft(1194649));
+        assert!(!is_prime_qft(12327121));
+        assert!(!is_prime_qft(18446744073709551615));
+        for x in 0..10000u64 {
+            assert_eq!(is_prime_qft(x), crate::is_prime_32(x as u32));
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use crate::check::is_prime;
+use crate::progression::{gcd, ResiduePrimes};
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::progression::ResiduePrimes128;
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wide::is_prime_128;
+
+/*
+   Random primes
+
+   Candidates congruent to a (mod q) are drawn uniformly from the interval and rejected until one is prime, so that
+   every prime satisfying the constraints is equally likely. This differs from taking next_prime of a random integer,
+   which favours primes that follow large gaps. When q is odd the candidates are restricted to the odd integers
+   by lifting the progression to modulus 2q, with 2 handled as a single additional candidate.
+
+   Each candidate is reduced once by the product of the odd primes up to 53 so that trial division is performed in
+   single-word arithmetic, followed by is_prime. Intervals with few candidates are enumerated with ResiduePrimes instead,
+   which terminates when no prime exists.
+*/
+
+/// Product of the odd primes 3 through 53
+const SMALL_PRODUCT: u64 = 16294579238595022365;
+
+const SMALL_PRIMES: [u64; 15] = [3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+
+/// Intervals with at most this many candidates are enumerated
+const EXHAUSTIVE: u128 = 1 << 12;
+
+/// Maximum number of candidates drawn before giving up
+const MAX_DRAWS: u32 = 1 << 20;
+
+/// Source of uniformly distributed random integers
+///
+/// Implement this for any generator to use it with the random prime functions, the crate provides SplitMix64 for
+/// reproducible sequences.
+pub trait RandomSource {
+    /// Uniformly distributed 64-bit integer
+    fn next_u64(&mut self) -> u64;
+
+    /// Uniformly distributed 128-bit integer
+    fn next_u128(&mut self) -> u128 {
+        (self.next_u64() as u128) << 64 | self.next_u64() as u128
+    }
+}
+
+impl<R: RandomSource + ?Sized> RandomSource for &mut R {
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+
+    fn next_u128(&mut self) -> u128 {
+        (**self).next_u128()
+    }
+}
+
+/// SplitMix64 generator
+///
+/// Deterministic for a given seed, use it to generate reproducible test vectors. It is not cryptographically secure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Generator with the given seed
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+}
+
+// In: K > 0
+// Out: Uniformly distributed integer in [0,K)
+fn uniform_below<R: RandomSource>(k: u128, rng: &mut R) -> u128 {
+    if k == 1 {
+        return 0;
+    }
+    let mask = u128::MAX >> (k - 1).leading_zeros();
+    loop {
+        let r = if mask <= u64::MAX as u128 { rng.next_u64() as u128 & mask } else { rng.next_u128() & mask };
+        if r < k {
+            return r;
+        }
+    }
+}
+
+// Out: True if X > 53 has an odd prime factor no greater than 53
+#[inline]
+fn small_factor(x: u128) -> bool {
+    let r = (x % SMALL_PRODUCT as u128) as u64;
+    x > 53 && SMALL_PRIMES.iter().any(|&p| r.is_multiple_of(p))
+}
+
+// Candidates first + k*step for k < count, plus 2 if two is set
+#[derive(Clone, Copy, Debug)]
+struct Candidates {
+    first: u128,
+    step: u128,
+    count: u128,
+    two: bool,
+}
+
+impl Candidates {
+    // In: [lo,hi), residue < modulus with gcd(residue,modulus) = 1, hi <= max + 1
+    const fn new(lo: u128, hi: u128, residue: u128, modulus: u128) -> Self {
+        let mut candidates = Self { first: 0, step: modulus, count: 0, two: false };
+        if hi <= lo {
+            return candidates;
+        }
+        let (mut residue, mut step) = (residue, modulus);
+        // Lift to the odd members of the progression, keeping 2 aside
+        if modulus & 1 == 1 && modulus <= u128::MAX / 2 {
+            candidates.two = lo <= 2 && 2 < hi && 2 % modulus == residue;
+            if residue & 1 == 0 {
+                residue += modulus;
+            }
+            step = 2 * modulus;
+        }
+        candidates.step = step;
+        // Least candidate >= lo
+        let r = lo % step;
+        let up = if residue >= r { residue - r } else { residue + (step - r) };
+        let first = match lo.checked_add(up) {
+            Some(first) => first,
+            None => return candidates,
+        };
+        if first < hi {
+            candidates.first = first;
+            candidates.count = (hi - 1 - first) / step + 1;
+        }
+        candidates
+    }
+
+    const fn total(&self) -> u128 {
+        self.count + self.two as u128
+    }
+
+    // In: K < total
+    const fn get(&self, k: u128) -> u128 {
+        if k == self.count {
+            return 2;
+        }
+        self.first + k * self.step
+    }
+
+    // In: Primality test, enumeration of the primes among the candidates
+    // Out: Uniformly selected prime among the candidates
+    fn sample<R: RandomSource, I: Iterator<Item = u128> + Clone>(
+        &self,
+        rng: &mut R,
+        test: impl Fn(u128) -> bool,
+        primes: I,
+    ) -> Option<u128> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+        if total <= EXHAUSTIVE {
+            let count = primes.clone().count() as u128;
+            if count == 0 {
+                return None;
+            }
+            return primes.clone().nth(uniform_below(count, rng) as usize);
+        }
+        for _ in 0..MAX_DRAWS {
+            let x = self.get(uniform_below(total, rng));
+            if !small_factor(x) && test(x) {
+                return Some(x);
+            }
+        }
+        None
+    }
+}
+
+/// Uniform sampler of 64-bit primes
+///
+/// Samples primes from an interval, optionally restricted to a residue class
+///
+/// ```ignore
+/// use machine_prime::{PrimeSampler,SplitMix64};
+///
+/// let mut rng = SplitMix64::new(42);
+/// // 62-bit primes congruent to 3 mod 4
+/// let p = PrimeSampler::bits(62).unwrap().congruent(3,4).unwrap().sample(&mut rng);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrimeSampler {
+    lo: u64,
+    hi: u64,
+    residue: u64,
+    modulus: u64,
+}
+
+impl PrimeSampler {
+    /// Primes in [lo,hi)
+    pub const fn new(lo: u64, hi: u64) -> Self {
+        Self { lo, hi, residue: 0, modulus: 1 }
+    }
+
+    /// Primes of exactly the given bit length
+    ///
+    /// Out: Sampler over [2^(bits-1),2^bits), None unless 2 <= bits <= 64
+    pub const fn bits(bits: u32) -> Option<Self> {
+        if bits < 2 || bits > 64 {
+            return None;
+        }
+        // 2^64-1 is composite so the interval can exclude it
+        let hi = if bits == 64 { u64::MAX } else { 1 << bits };
+        Some(Self::new(1 << (bits - 1), hi))
+    }
+
+    /// Restrict to primes p ≡ residue (mod modulus)
+    ///
+    /// Out: None if the modulus is zero or gcd(residue,modulus) != 1
+    pub const fn congruent(self, residue: u64, modulus: u64) -> Option<Self> {
+        if modulus == 0 {
+            return None;
+        }
+        let residue = residue % modulus;
+        if gcd(residue as u128, modulus as u128) != 1 {
+            return None;
+        }
+        Some(Self { residue, modulus, ..self })
+    }
+
+    /// Uniformly sampled prime
+    ///
+    /// Out: Prime satisfying the constraints, None if there is none, or none was found in 2^20 draws
+    pub fn sample<R: RandomSource>(&self, rng: &mut R) -> Option<u64> {
+        let candidates = Candidates::new(self.lo as u128, self.hi as u128, self.residue as u128, self.modulus as u128);
+        let primes = ResiduePrimes::new(self.residue, self.modulus, self.lo, self.hi)?.map(|p| p as u128);
+        candidates.sample(rng, |x| is_prime(x as u64), primes).map(|p| p as u64)
+    }
+}
+
+/// Uniform sampler of 128-bit primes
+///
+/// Samples primes from an interval, optionally restricted to a residue class
+#[cfg(any(feature = "wide", feature = "qft"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrimeSampler128 {
+    lo: u128,
+    hi: u128,
+    residue: u128,
+    modulus: u128,
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl PrimeSampler128 {
+    /// Primes in [lo,hi)
+    pub const fn new(lo: u128, hi: u128) -> Self {
+        Self { lo, hi, residue: 0, modulus: 1 }
+    }
+
+    /// Primes of exactly the given bit length
+    ///
+    /// Out: Sampler over [2^(bits-1),2^bits), None unless 2 <= bits <= 128
+    pub const fn bits(bits: u32) -> Option<Self> {
+        if bits < 2 || bits > 128 {
+            return None;
+        }
+        // 2^128-1 is composite so the interval can exclude it
+        let hi = if bits == 128 { u128::MAX } else { 1 << bits };
+        Some(Self::new(1 << (bits - 1), hi))
+    }
+
+    /// Restrict to primes p ≡ residue (mod modulus)
+    ///
+    /// Out: None if the modulus is zero or gcd(residue,modulus) != 1
+    pub const fn congruent(self, residue: u128, modulus: u128) -> Option<Self> {
+        if modulus == 0 {
+            return None;
+        }
+        let residue = residue % modulus;
+        if gcd(residue, modulus) != 1 {
+            return None;
+        }
+        Some(Self { residue, modulus, ..self })
+    }
+
+    /// Uniformly sampled prime
+    ///
+    /// Out: Prime satisfying the constraints, None if there is none, or none was found in 2^20 draws
+    pub fn sample<R: RandomSource>(&self, rng: &mut R) -> Option<u128> {
+        let candidates = Candidates::new(self.lo, self.hi, self.residue, self.modulus);
+        let primes = ResiduePrimes128::new(self.residue, self.modulus, self.lo, self.hi)?;
+        candidates.sample(rng, |x| is_prime_128(x), primes)
+    }
+}
+
+/// Random prime of exact bit length
+///
+/// In: Bit length 2 <= bits <= 64, random source
+///
+/// Out: Uniformly distributed prime in [2^(bits-1),2^bits), None if bits is out of range, or none was found in 2^20 draws
+pub fn random_prime<R: RandomSource>(bits: u32, rng: &mut R) -> Option<u64> {
+    PrimeSampler::bits(bits)?.sample(rng)
+}
+
+/// Random prime in an interval
+///
+/// In: [lo,hi), random source
+///
+/// Out: Uniformly distributed prime in [lo,hi), None if there is none, or none was found in 2^20 draws
+pub fn random_prime_in<R: RandomSource>(lo: u64, hi: u64, rng: &mut R) -> Option<u64> {
+    PrimeSampler::new(lo, hi).sample(rng)
+}
+
+/// Random prime of exact bit length, 128-bit form
+///
+/// In: Bit length 2 <= bits <= 128, random source
+///
+/// Out: Uniformly distributed prime in [2^(bits-1),2^bits), None if bits is out of range, or none was found in 2^20 draws
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub fn random_prime_128<R: RandomSource>(bits: u32, rng: &mut R) -> Option<u128> {
+    PrimeSampler128::bits(bits)?.sample(rng)
+}
+
+/// Random prime in an interval, 128-bit form
+///
+/// In: [lo,hi), random source
+///
+/// Out: Uniformly distributed prime in [lo,hi), None if there is none, or none was found in 2^20 draws
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub fn random_prime_in_128<R: RandomSource>(lo: u128, hi: u128, rng: &mut R) -> Option<u128> {
+    PrimeSampler128::new(lo, hi).sample(rng)
+}
@@ -0,0 +1,229 @@
+use crate::check::{is_prime, is_prime_wc};
+use crate::constellation::{SIEVE_MAX, SIEVING_PRIMES};
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wide::is_prime_wc_128;
+
+/*
+   Safe primes and Sophie Germain primes
+
+   q is a Sophie Germain prime if q and 2q+1 are both prime, 2q+1 is then a safe prime. Candidates q are sieved
+   in blocks, clearing q whenever q or 2q+1 has a factor among 2 and the first 128 odd primes, i.e q ≡ 0 or
+   q ≡ (p-1)/2 (mod p). About 1 in 90 candidates survives, each is confirmed by is_prime_wc on q and then 2q+1.
+   In 128-bit is_prime_wc_128 performs the confirmation, which includes Khashin's quadratic Frobenius test when
+   the QFT feature is enabled.
+*/
+
+/// Number of candidates sieved at a time by next_sophie_germain
+const BLOCK_WORDS: usize = 64;
+
+/// Greatest q such that 2q+1 < 2^64
+const MAX_Q: u64 = (u64::MAX - 1) / 2;
+
+/// Greatest q such that 2q+1 < 2^128
+#[cfg(any(feature = "wide", feature = "qft"))]
+const MAX_Q_128: u128 = (u128::MAX - 1) / 2;
+
+// In: [lo,lo+len) with lo+len-1 <= (2^128-2)/2, bitmap of at least ceil(len/64) words
+// Out: Bit i is cleared if lo+i or 2(lo+i)+1 has a small factor other than itself
+fn sieve_block(lo: u128, len: usize, bitmap: &mut [u64]) {
+    let words = len.div_ceil(64);
+    bitmap[..words].fill(u64::MAX);
+    if len & 63 != 0 {
+        bitmap[words - 1] = (1u64 << (len & 63)) - 1;
+    }
+    // 0 and 1 are not prime
+    for q in lo..(lo + len as u128).min(2) {
+        let i = (q - lo) as usize;
+        bitmap[i >> 6] &= !(1 << (i & 63));
+    }
+
+    for &p in SIEVING_PRIMES.iter() {
+        let p = p as u128;
+        // q ≡ 0 (mod p), excluding q = p, q = 0 has been cleared
+        let mut i = ((p - lo % p) % p) as usize;
+        while lo + (i as u128) < 2 * p {
+            i += p as usize;
+        }
+        while i < len {
+            bitmap[i >> 6] &= !(1 << (i & 63));
+            i += p as usize;
+        }
+        if p == 2 {
+            continue;
+        }
+        // 2q+1 ≡ 0 (mod p), excluding 2q+1 = p
+        let half = (p - 1) / 2;
+        let mut i = ((half + p - lo % p) % p) as usize;
+        if lo + (i as u128) == half {
+            i += p as usize;
+        }
+        while i < len {
+            bitmap[i >> 6] &= !(1 << (i & 63));
+            i += p as usize;
+        }
+    }
+}
+
+// In: Q that survived the sieve, 2Q+1 < 2^64
+// Out: Q is a Sophie Germain prime
+#[inline]
+fn confirm(q: u64) -> bool {
+    if q <= SIEVE_MAX {
+        return is_prime(q) && is_prime(2 * q + 1);
+    }
+    is_prime_wc(q) && is_prime_wc(2 * q + 1)
+}
+
+// In: Q that survived the sieve, 2Q+1 < 2^128
+// Out: Q is a Sophie Germain prime
+#[cfg(any(feature = "wide", feature = "qft"))]
+#[inline]
+fn confirm_128(q: u128) -> bool {
+    if q <= SIEVE_MAX as u128 {
+        return is_prime(q as u64) && is_prime(2 * q as u64 + 1);
+    }
+    is_prime_wc_128(q) && is_prime_wc_128(2 * q + 1)
+}
+
+// In: Bitmap of [lo,lo+len) from sieve_block
+// Out: Clears the survivors that are not Sophie Germain primes, returns the count
+fn confirm_block(lo: u128, len: usize, bitmap: &mut [u64], test: impl Fn(u128) -> bool) -> u64 {
+    let mut count = 0;
+    for (idx, word) in bitmap[..len.div_ceil(64)].iter_mut().enumerate() {
+        let mut bits = *word;
+        while bits != 0 {
+            let offset = bits.trailing_zeros();
+            bits &= bits - 1;
+            if test(lo + ((idx as u128) << 6) + offset as u128) {
+                count += 1;
+            } else {
+                *word &= !(1 << offset);
+            }
+        }
+    }
+    count
+}
+
+/// Bitmap of the Sophie Germain primes in an interval
+///
+/// In: [lo,hi), bitmap of at least ceil((hi-lo)/64) words
+///
+/// Out: Bit i of the bitmap is set if lo + i and 2(lo + i) + 1 are both prime and less than 2^64, None if the
+///      bitmap is too small. Returns the number of Sophie Germain primes in the interval
+pub fn sophie_germain_bitmap(lo: u64, hi: u64, bitmap: &mut [u64]) -> Option<u64> {
+    if hi <= lo {
+        return Some(0);
+    }
+    let len = hi - lo;
+    if len.div_ceil(64) > bitmap.len() as u64 {
+        return None;
+    }
+    let words = len.div_ceil(64) as usize;
+    // Candidates whose safe prime would exceed 2^64 are excluded
+    let valid = if lo > MAX_Q { 0 } else { (hi - 1).min(MAX_Q) - lo + 1 };
+    bitmap[..words].fill(0);
+    if valid == 0 {
+        return Some(0);
+    }
+    sieve_block(lo as u128, valid as usize, bitmap);
+    Some(confirm_block(lo as u128, valid as usize, bitmap, |q| confirm(q as u64)))
+}
+
+/// Bitmap of the Sophie Germain primes in an interval, 128-bit form
+///
+/// In: [lo,hi), bitmap of at least ceil((hi-lo)/64) words
+///
+/// Out: Bit i of the bitmap is set if lo + i and 2(lo + i) + 1 are both prime and less than 2^128, None if the
+///      bitmap is too small. Returns the number of Sophie Germain primes in the interval
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub fn sophie_germain_bitmap_128(lo: u128, hi: u128, bitmap: &mut [u64]) -> Option<u64> {
+    if hi <= lo {
+        return Some(0);
+    }
+    let len = hi - lo;
+    if len.div_ceil(64) > bitmap.len() as u128 {
+        return None;
+    }
+    let words = len.div_ceil(64) as usize;
+    let valid = if lo > MAX_Q_128 { 0 } else { (hi - 1).min(MAX_Q_128) - lo + 1 };
+    bitmap[..words].fill(0);
+    if valid == 0 {
+        return Some(0);
+    }
+    sieve_block(lo, valid as usize, bitmap);
+    Some(confirm_block(lo, valid as usize, bitmap, confirm_128))
+}
+
+/// Next Sophie Germain prime
+///
+/// In: N
+///
+/// Out: Least prime Q > N such that 2Q+1 is prime, None if 2Q+1 > 2^64
+pub fn next_sophie_germain(n: u64) -> Option<u64> {
+    let mut bitmap = [0u64; BLOCK_WORDS];
+    let mut lo = n.checked_add(1)?;
+    while lo <= MAX_Q {
+        let len = (MAX_Q - lo + 1).min(64 * BLOCK_WORDS as u64) as usize;
+        sieve_block(lo as u128, len, &mut bitmap);
+        for (idx, &word) in bitmap[..len.div_ceil(64)].iter().enumerate() {
+            let mut bits = word;
+            while bits != 0 {
+                let q = lo + ((idx as u64) << 6) + bits.trailing_zeros() as u64;
+                bits &= bits - 1;
+                if confirm(q) {
+                    return Some(q);
+                }
+            }
+        }
+        lo += len as u64;
+    }
+    None
+}
+
+/// Next safe prime
+///
+/// In: N
+///
+/// Out: Least prime P > N such that (P-1)/2 is prime, None if P > 2^64
+pub fn next_safe_prime(n: u64) -> Option<u64> {
+    // 2q+1 > n if and only if q > (n-1)/2
+    next_sophie_germain(n.saturating_sub(1) / 2).map(|q| 2 * q + 1)
+}
+
+/// Next Sophie Germain prime, 128-bit form
+///
+/// In: N
+///
+/// Out: Least prime Q > N such that 2Q+1 is prime, None if 2Q+1 > 2^128
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub fn next_sophie_germain_128(n: u128) -> Option<u128> {
+    let mut bitmap = [0u64; BLOCK_WORDS];
+    let mut lo = n.checked_add(1)?;
+    while lo <= MAX_Q_128 {
+        let len = (MAX_Q_128 - lo + 1).min(64 * BLOCK_WORDS as u128) as usize;
+        sieve_block(lo, len, &mut bitmap);
+        for (idx, &word) in bitmap[..len.div_ceil(64)].iter().enumerate() {
+            let mut bits = word;
+            while bits != 0 {
+                let q = lo + ((idx as u128) << 6) + bits.trailing_zeros() as u128;
+                bits &= bits - 1;
+                if confirm_128(q) {
+                    return Some(q);
+                }
+            }
+        }
+        lo += len as u128;
+    }
+    None
+}
+
+/// Next safe prime, 128-bit form
+///
+/// In: N
+///
+/// Out: Least prime P > N such that (P-1)/2 is prime, None if P > 2^128
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub fn next_safe_prime_128(n: u128) -> Option<u128> {
+    next_sophie_germain_128(n.saturating_sub(1) / 2).map(|q| 2 * q + 1)
+}
@@ -0,0 +1,331 @@
+use crate::batch::is_prime_batch;
+
+/*
+   Segmented sieve of Eratosthenes
+
+   The interval [lo,hi) is represented as a bitmap supplied by the caller, bit i of the bitmap corresponds to
+   lo + i and is set if lo + i is prime. No memory is allocated, the sieving primes are themselves generated
+   on the stack in blocks, using the primes under 2^16 which are sieved once per call.
+
+   Sieving with every prime up to sqrt(hi) is wasteful when the interval is short relative to its position,
+   e.g [2^64-2^20,2^64) would require sieving by the 203 million primes under 2^32 to evaluate a million
+   integers. Instead the sieving bound is limited to roughly the length of the interval (and never less than 2^16),
+   and any survivors that could still be composite are evaluated with is_prime_batch. Sieving with p costs about
+   one division to locate its first multiple, while a survivor costs a strong fermat test, so the balance is reached
+   when the number of sieving primes is comparable to the number of survivors.
+
+   The multiples of 3,5,7,11 and 13 are removed with precomputed word masks rather than bit by bit, they account
+   for about a third of the bits cleared by the sieve.
+*/
+
+/// Odd integers under 2^16 as a bitmap of 2^15 bits, bit i represents 2i+1
+const SMALL_WORDS: usize = 512;
+
+/// Block of odd integers used to generate sieving primes above 2^16
+const BLOCK_WORDS: usize = 1024;
+
+const SMALL_BOUND: u64 = 1 << 16;
+
+/// Survivors evaluated per call to is_prime_batch
+const CONFIRM_BLOCK: usize = 64;
+
+// Out: Bitmap of the odd primes under 2^16
+const fn small_sieve() -> [u64; SMALL_WORDS] {
+    let mut composite = [0u64; SMALL_WORDS];
+    // 1 is not prime
+    composite[0] |= 1;
+    let mut p = 3u64;
+    while p * p < SMALL_BOUND {
+        let i = p >> 1;
+        if composite[(i >> 6) as usize] >> (i & 63) & 1 == 0 {
+            let mut m = p * p;
+            while m < SMALL_BOUND {
+                let j = m >> 1;
+                composite[(j >> 6) as usize] |= 1 << (j & 63);
+                m += 2 * p;
+            }
+        }
+        p += 2;
+    }
+    composite
+}
+
+/// Composite bitmap of the odd integers under 2^16
+static SMALL_COMPOSITE: [u64; SMALL_WORDS] = small_sieve();
+
+/// Primes removed a word at a time before sieving
+const PRESIEVE: [u64; 5] = [3, 5, 7, 11, 13];
+
+// Out: For each presieving prime p and residue r < p, the word whose bit i is clear if p divides r+i
+const fn presieve_masks() -> [[u64; 13]; 5] {
+    let mut masks = [[0u64; 13]; 5];
+    let mut k = 0;
+    while k < 5 {
+        let p = PRESIEVE[k];
+        let mut r = 0;
+        while r < p {
+            let mut mask = u64::MAX;
+            let mut i = 0;
+            while i < 64 {
+                if (r + i) % p == 0 {
+                    mask &= !(1 << i);
+                }
+                i += 1;
+            }
+            masks[k][r as usize] = mask;
+            r += 1;
+        }
+        k += 1;
+    }
+    masks
+}
+
+/// Word masks of the presieving primes, indexed by the residue of the first integer in the word
+static PRESIEVE_MASK: [[u64; 13]; 5] = presieve_masks();
+
+/// Generator of the odd primes up to a bound no greater than 2^32
+pub(crate) struct SievingPrimes {
+    bound: u64,
+    // Current position, odd integer
+    next: u64,
+    // Start of the current block and its composite bitmap, only used above 2^16
+    block_lo: u64,
+    block: [u64; BLOCK_WORDS],
+}
+
+impl SievingPrimes {
+    /// Odd primes p <= bound
+    pub(crate) fn new(bound: u64) -> Self {
+        Self {
+            bound: bound.min(u32::MAX as u64),
+            next: 3,
+            block_lo: 0,
+            block: [0; BLOCK_WORDS],
+        }
+    }
+
+    // Sieve the block of odd integers starting at lo
+    fn fill_block(&mut self, lo: u64) {
+        self.block = [0; BLOCK_WORDS];
+        self.block_lo = lo;
+        let hi = lo + 128 * BLOCK_WORDS as u64;
+
+        let mut i = 1;
+        while i < SMALL_WORDS as u64 * 64 {
+            if SMALL_COMPOSITE[(i >> 6) as usize] >> (i & 63) & 1 == 0 {
+                let p = 2 * i + 1;
+                if p * p >= hi {
+                    break;
+                }
+                // First odd multiple of p that is at least lo and p^2
+                let mut m = lo.div_ceil(p) * p;
+                if m & 1 == 0 {
+                    m += p;
+                }
+                if m < p * p {
+                    m = p * p;
+                }
+                while m < hi {
+                    let j = (m - lo) >> 1;
+                    self.block[(j >> 6) as usize] |= 1 << (j & 63);
+                    m += 2 * p;
+                }
+            }
+            i += 1;
+        }
+    }
+}
+
+impl Iterator for SievingPrimes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while self.next <= self.bound {
+            let x = self.next;
+            self.next += 2;
+            if x < SMALL_BOUND {
+                let i = x >> 1;
+                if SMALL_COMPOSITE[(i >> 6) as usize] >> (i & 63) & 1 == 0 {
+                    return Some(x);
+                }
+                continue;
+            }
+            if x >= self.block_lo + 128 * BLOCK_WORDS as u64 || self.block_lo == 0 {
+                self.fill_block(x);
+            }
+            let j = (x - self.block_lo) >> 1;
+            if self.block[(j >> 6) as usize] >> (j & 63) & 1 == 0 {
+                return Some(x);
+            }
+        }
+        None
+    }
+}
+
+// In: lo, len <= 64*bitmap.len()
+// Out: Bitmap of the odd integers in [lo,lo+len) with composites cleared up to the sieving bound
+//      Returns the bound, every set bit less than bound^2 is prime
+fn sieve_segment(lo: u64, len: u64, bitmap: &mut [u64]) -> u64 {
+    let words = len.div_ceil(64) as usize;
+
+    // Odd positions relative to lo
+    let pattern = if lo & 1 == 0 { 0xAAAAAAAAAAAAAAAA } else { 0x5555555555555555 };
+    for word in bitmap[..words].iter_mut() {
+        *word = pattern;
+    }
+    // Clear the bits past the interval
+    if len & 63 != 0 {
+        bitmap[words - 1] &= (1u64 << (len & 63)) - 1;
+    }
+
+    // Multiples of the presieving primes, cheaper as masks than as individual bits
+    let mut residue = [0u64; 5];
+    for k in 0..5 {
+        residue[k] = lo % PRESIEVE[k];
+    }
+    for word in bitmap[..words].iter_mut() {
+        for k in 0..5 {
+            *word &= PRESIEVE_MASK[k][residue[k] as usize];
+            residue[k] = (residue[k] + 64) % PRESIEVE[k];
+        }
+    }
+
+    let hi = lo + (len - 1);
+    let sqrt = hi.isqrt();
+    let bound = sqrt.min(len.max(SMALL_BOUND));
+
+    // The presieve also removed the presieving primes themselves
+    for p in PRESIEVE {
+        if lo <= p && p <= hi {
+            bitmap[((p - lo) >> 6) as usize] |= 1 << ((p - lo) & 63);
+        }
+    }
+
+    for p in SievingPrimes::new(bound) {
+        if p <= 13 {
+            continue;
+        }
+        // First odd multiple of p that is at least lo and p^2, the even multiples are already cleared
+        let start = if lo > p * p { lo } else { p * p };
+        let mut m = match start.div_ceil(p).checked_mul(p) {
+            Some(m) => m,
+            None => continue,
+        };
+        if m & 1 == 0 {
+            m = match m.checked_add(p) {
+                Some(m) => m,
+                None => continue,
+            };
+        }
+        while m <= hi {
+            let j = m - lo;
+            bitmap[(j >> 6) as usize] &= !(1 << (j & 63));
+            m = match m.checked_add(2 * p) {
+                Some(m) => m,
+                None => break,
+            };
+        }
+    }
+
+    // 1 is not prime and 2 was removed with the even integers
+    if lo <= 1 && 1 <= hi {
+        bitmap[0] &= !(1 << (1 - lo));
+    }
+    if lo <= 2 && 2 <= hi {
+        bitmap[0] |= 1 << (2 - lo);
+    }
+    bound
+}
+
+// In: Bitmap from sieve_segment, sieving bound
+// Out: Clears the survivors that are composite
+fn confirm_segment(lo: u64, len: u64, bitmap: &mut [u64], bound: u64) {
+    // Survivors less than bound^2 have no prime factor below their square root
+    let start = match bound.checked_mul(bound) {
+        Some(sqr) if sqr > lo => sqr - lo,
+        Some(_) => 0,
+        None => return,
+    };
+    if start >= len {
+        return;
+    }
+    let words = len.div_ceil(64) as usize;
+
+    // Survivors are mostly prime, so they are evaluated together to interleave their strong fermat tests
+    let mut candidate = [0u64; CONFIRM_BLOCK];
+    let mut flag = [false; CONFIRM_BLOCK];
+    let mut count = 0;
+
+    for idx in (start >> 6) as usize..words {
+        let mut bits = bitmap[idx];
+        while bits != 0 {
+            let offset = bits.trailing_zeros() as u64;
+            bits &= bits - 1;
+            let x = lo + ((idx as u64) << 6) + offset;
+            if x - lo >= start {
+                candidate[count] = x;
+                count += 1;
+            }
+            if count == CONFIRM_BLOCK {
+                clear_composites(lo, &candidate, &mut flag, bitmap);
+                count = 0;
+            }
+        }
+    }
+    clear_composites(lo, &candidate[..count], &mut flag[..count], bitmap);
+}
+
+// In: Survivors of the sieve
+// Out: Clears the survivors that are composite
+fn clear_composites(lo: u64, candidate: &[u64], flag: &mut [bool], bitmap: &mut [u64]) {
+    is_prime_batch(candidate, flag);
+    for (x, prime) in candidate.iter().zip(flag.iter()) {
+        if !prime {
+            let j = x - lo;
+            bitmap[(j >> 6) as usize] &= !(1 << (j & 63));
+        }
+    }
+}
+
+/// Bitmap of the primes in an interval
+///
+/// In: [lo,hi), bitmap of at least ceil((hi-lo)/64) words
+///
+/// Out: Bit i of the bitmap is set if lo + i is prime, None if the bitmap is too small.
+///      Returns the number of primes in the interval
+pub fn prime_bitmap(lo: u64, hi: u64, bitmap: &mut [u64]) -> Option<u64> {
+    if hi <= lo {
+        return Some(0);
+    }
+    let len = hi - lo;
+    if len.div_ceil(64) > bitmap.len() as u64 {
+        return None;
+    }
+    let bound = sieve_segment(lo, len, bitmap);
+    confirm_segment(lo, len, bitmap, bound);
+
+    let words = len.div_ceil(64) as usize;
+    Some(bitmap[..words].iter().map(|w| w.count_ones() as u64).sum())
+}
+
+/// Number of primes in an interval
+///
+/// In: [lo,hi), workspace
+///
+/// Out: Number of primes in [lo,hi). The interval is evaluated in segments of 64*workspace.len() integers,
+///      None if the workspace is empty
+pub fn prime_count_interval(lo: u64, hi: u64, workspace: &mut [u64]) -> Option<u64> {
+    if workspace.is_empty() {
+        return None;
+    }
+    let segment = 64 * workspace.len() as u64;
+    let mut count = 0;
+    let mut start = lo;
+
+    while start < hi {
+        let end = if hi - start > segment { start + segment } else { hi };
+        count += prime_bitmap(start, end, workspace)?;
+        start = end;
+    }
+    Some(count)
+}
@@ -0,0 +1,146 @@
+use core::simd::cmp::{SimdPartialEq, SimdPartialOrd};
+use core::simd::num::SimdUint;
+use core::simd::{Mask, Select, Simd};
+
+use crate::check::{mul_inv2, one_mont, to_mont, two_mont};
+
+#[cfg(feature = "ssmr")]
+use crate::hashbase::FERMAT_WITNESS;
+
+/*
+   Lane-parallel strong fermat test
+
+   core::simd has no widening 64-bit multiply so the 128-bit product is assembled from four 32x32 bit products,
+   which AVX2 and AVX-512 evaluate with a single instruction per vector. The ladder is branch-free, lanes that
+   do not multiply at a given bit select their previous value, so every lane follows the same instruction stream.
+
+   The Montgomery constants are computed per lane with the scalar functions, they require a division which has
+   no vector instruction on any target.
+*/
+
+// In: X,Y
+// Out: X*Y mod 2^64, X*Y/2^64
+#[inline(always)]
+fn widening_mul_simd<const N: usize>(x: Simd<u64, N>, y: Simd<u64, N>) -> (Simd<u64, N>, Simd<u64, N>) {
+    let mask = Simd::splat(0xFFFFFFFF);
+    let (x0, x1) = (x & mask, x >> 32);
+    let (y0, y1) = (y & mask, y >> 32);
+
+    let lolo = x0 * y0;
+    let lohi = x0 * y1;
+    let hilo = x1 * y0;
+    let hihi = x1 * y1;
+
+    // Sum of the middle words, at most 3*(2^32-1) so it cannot overflow
+    let mid = (lolo >> 32) + (lohi & mask) + (hilo & mask);
+
+    let lo = (lolo & mask) | (mid << 32);
+    let hi = hihi + (lohi >> 32) + (hilo >> 32) + (mid >> 32);
+    (lo, hi)
+}
+
+/// Subtraction in Montgomery form, lane-parallel
+///
+/// In: X,Y,N
+///
+/// Out: X-Y mod N
+#[inline(always)]
+pub fn mont_sub_simd<const N: usize>(x: Simd<u64, N>, y: Simd<u64, N>, n: Simd<u64, N>) -> Simd<u64, N> {
+    let diff = x - y;
+    x.simd_lt(y).select(diff + n, diff)
+}
+
+/// Product in Montgomery form, lane-parallel
+///
+/// In: Mont(X,N),Mont(Y,N), N^-1,N
+///
+/// Out: Mont(X*Y,N)
+#[inline(always)]
+pub fn mont_prod_simd<const N: usize>(
+    x: Simd<u64, N>,
+    y: Simd<u64, N>,
+    inv: Simd<u64, N>,
+    n: Simd<u64, N>,
+) -> Simd<u64, N> {
+    let (lo, hi) = widening_mul_simd(x, y);
+    let (_, borrow) = widening_mul_simd(lo * inv, n);
+    mont_sub_simd(hi, borrow, n)
+}
+
+/// Fermat witness selection for n < 2^64, lane-parallel
+#[cfg(feature = "ssmr")]
+#[inline]
+pub fn witness_selector_simd<const N: usize>(x: Simd<u64, N>) -> Simd<u64, N> {
+    let idx = (x.cast::<u32>() * Simd::splat(811484239)) >> 14;
+    Simd::gather_or_default(&FERMAT_WITNESS, idx.cast::<usize>()).cast::<u64>()
+}
+
+/// Strong Fermat test, lane-parallel
+///
+/// In: N \in 2Z+1, base, mask of active lanes
+///
+/// Out: SPRP(N,base) for each active lane, inactive lanes are false
+pub fn strong_fermat_simd<const N: usize>(
+    n: Simd<u64, N>,
+    base: Simd<u64, N>,
+    active: Mask<i64, N>,
+) -> Mask<i64, N> {
+    let mut inv = [0u64; N];
+    let mut one = [0u64; N];
+    let mut mbase = [0u64; N];
+
+    for i in 0..N {
+        if !active.test(i) {
+            continue;
+        }
+        let (x, b) = (n[i], base[i]);
+        inv[i] = mul_inv2(x);
+        one[i] = one_mont(x);
+        mbase[i] = if b == 2 { two_mont(one[i], x) } else { to_mont(b, x) };
+    }
+
+    let inv = Simd::from_array(inv);
+    let one = Simd::from_array(one);
+    let oneinv = n - one;
+    let zero = Simd::splat(0);
+
+    let tz = (n - Simd::splat(1)).trailing_zeros();
+    let d = n >> tz.cast::<u64>();
+    let bits = active.select(Simd::splat(64) - d.leading_zeros(), Simd::splat(0)).reduce_max();
+    let maxtz = active.select(tz, Simd::splat(0)).reduce_max();
+
+    // Powers for a left-to-right ladder over 2-bit digits, 1.5 products per bit instead of 2
+    let b1 = Simd::from_array(mbase);
+    let b2 = mont_prod_simd(b1, b1, inv, n);
+    let b3 = mont_prod_simd(b2, b1, inv, n);
+
+    // Lanes with shorter exponents have leading zero digits, which leave Mont(1) unchanged
+    let mut result = one;
+    let mut shift = bits.div_ceil(2) * 2;
+
+    while shift > 0 {
+        shift -= 2;
+        result = mont_prod_simd(result, result, inv, n);
+        result = mont_prod_simd(result, result, inv, n);
+
+        let digit = (d >> Simd::splat(shift)) & Simd::splat(3);
+        let factor = digit
+            .simd_eq(Simd::splat(1))
+            .select(b1, digit.simd_eq(Simd::splat(2)).select(b2, b3));
+        result = digit.simd_ne(zero).select(mont_prod_simd(result, factor, inv, n), result);
+    }
+
+    let mut pass = active & (result.simd_eq(one) | result.simd_eq(oneinv));
+
+    let mut count = 1;
+    while count < maxtz {
+        let live = active & !pass & Simd::splat(count).simd_lt(tz).cast::<i64>();
+        if !live.any() {
+            break;
+        }
+        result = live.select(mont_prod_simd(result, result, inv, n), result);
+        pass |= live & result.simd_eq(oneinv);
+        count += 1;
+    }
+    pass
+}
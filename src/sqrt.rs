@@ -0,0 +1,663 @@
+use crate::check::{add_mod, half_mod, is_prime, mont_pow, mont_prod, mont_sub, mul_inv2, one_mont, to_mont};
+use crate::factor::{gcd, Factorization};
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::factor_128::{gcd_128, Factorization128};
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wide::{add_mod_128, half_mod_128, is_prime_128, mont_pow_128, mont_prod_128, mont_sqr_128, mont_sub_128, mul_inv2_128, one_mont_128, to_mont_128};
+
+/*
+   Modular square roots
+
+   Modulo an odd prime p = 2^s q + 1 with q odd, Tonelli-Shanks takes about log2(p) + s^2/4 multiplications while
+   Cipolla's method takes about 3 log2(p) multiplications in F_p[√w], each costing several in F_p. Tonelli-Shanks is used
+   unless s^2 > 8 log2(p), i.e s > 22 for 64-bit primes, which only matters for primes such as NTT moduli
+   with large power of 2 factors. Both need a non-residue, which is found by trying 2, 3, ... with Euler's criterion.
+
+   Roots modulo p^k with p odd and p ∤ a are lifted from a root r modulo p by Newton's iteration for the inverse square
+   root, y <- y(3 - ay^2)/2, which avoids an inverse in each step and converges to y with ay^2 ≡ 1 (mod p^k), and the
+   root is then ay. Powers of p dividing a are removed in pairs. Modulo 2^k, odd squares are ≡ 1 (mod 8) and a root is
+   lifted one bit at a time.
+
+   Roots modulo composite N with known factorisation are combined by the Chinese remainder theorem. There are 2^ω(N)
+   or more roots, the one returned is the one constructed from the least root modulo each prime power.
+*/
+
+// In: A, N > 0
+// Out: A^-1 mod N, None if gcd(A,N) != 1
+pub(crate) const fn mod_inverse(a: u128, n: u128) -> Option<u128> {
+    // Extended Euclid over the magnitudes of the Bezout coefficients, whose signs alternate
+    let (mut r0, mut r1) = (n, a % n);
+    let (mut t0, mut t1) = (0u128, 1u128);
+    let mut negative = true;
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 + q * t1);
+        negative = !negative;
+    }
+    if r0 != 1 {
+        return None;
+    }
+    if negative && t0 != 0 {
+        return Some(n - t0);
+    }
+    Some(t0 % n)
+}
+
+// In: P odd prime, Mont(1,P), P^-1
+// Out: Mont(z,P) for the least quadratic non-residue z
+const fn non_residue(p: u64, one: u64, inv: u64) -> u64 {
+    let mut z = 2;
+    loop {
+        let zm = to_mont(z, p);
+        if mont_pow(zm, one, (p - 1) >> 1, inv, p) != one {
+            return zm;
+        }
+        z += 1;
+    }
+}
+
+// In: Mont(A,P) != 0, P odd prime with P-1 = 2^S Q
+// Out: Mont(√A,P), None if A is not a quadratic residue
+const fn tonelli_shanks(a: u64, p: u64, one: u64, inv: u64) -> Option<u64> {
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let mut c = mont_pow(non_residue(p, one, inv), one, q, inv, p);
+    let mut x = mont_pow(a, one, (q + 1) >> 1, inv, p);
+    let mut t = mont_pow(a, one, q, inv, p);
+    let mut m = s;
+    while t != one {
+        // Least i such that t^(2^i) = 1
+        let mut i = 0;
+        let mut t2 = t;
+        while t2 != one {
+            t2 = mont_prod(t2, t2, inv, p);
+            i += 1;
+            if i == m {
+                return None;
+            }
+        }
+        let mut b = c;
+        let mut j = 0;
+        while j + i + 1 < m {
+            b = mont_prod(b, b, inv, p);
+            j += 1;
+        }
+        x = mont_prod(x, b, inv, p);
+        c = mont_prod(b, b, inv, p);
+        t = mont_prod(t, c, inv, p);
+        m = i;
+    }
+    Some(x)
+}
+
+// In: Mont(A,P) != 0, P odd prime
+// Out: Mont(√A,P), None if A is not a quadratic residue
+const fn cipolla(a: u64, p: u64, one: u64, inv: u64) -> Option<u64> {
+    if mont_pow(a, one, (p - 1) >> 1, inv, p) != one {
+        return None;
+    }
+    // Least t such that w = t^2 - a is a non-residue
+    let mut t = one;
+    let mut w;
+    loop {
+        t = add_mod(t, one, p);
+        w = mont_sub(mont_prod(t, t, inv, p), a, p);
+        if w != 0 && mont_pow(w, one, (p - 1) >> 1, inv, p) != one {
+            break;
+        }
+    }
+    // (t + √w)^((p+1)/2) in F_p[√w] is √a
+    let (mut x0, mut x1) = (one, 0);
+    let (mut b0, mut b1) = (t, one);
+    let mut e = (p >> 1) + 1;
+    while e > 0 {
+        if e & 1 == 1 {
+            let y0 = add_mod(mont_prod(x0, b0, inv, p), mont_prod(mont_prod(x1, b1, inv, p), w, inv, p), p);
+            x1 = add_mod(mont_prod(x0, b1, inv, p), mont_prod(x1, b0, inv, p), p);
+            x0 = y0;
+        }
+        let c0 = add_mod(mont_prod(b0, b0, inv, p), mont_prod(mont_prod(b1, b1, inv, p), w, inv, p), p);
+        let prod = mont_prod(b0, b1, inv, p);
+        b1 = add_mod(prod, prod, p);
+        b0 = c0;
+        e >>= 1;
+    }
+    Some(x0)
+}
+
+// In: A < P, P odd prime
+// Out: Mont(√A,P), None if A is not a quadratic residue
+const fn sqrt_odd_prime(a: u64, p: u64, one: u64, inv: u64) -> Option<u64> {
+    let am = to_mont(a, p);
+    if am == 0 {
+        return Some(0);
+    }
+    let s = (p - 1).trailing_zeros();
+    if s * s > 8 * (u64::BITS - p.leading_zeros()) {
+        return cipolla(am, p, one, inv);
+    }
+    tonelli_shanks(am, p, one, inv)
+}
+
+/// Square root modulo a prime
+///
+/// In: A, P
+///
+/// Out: Least r such that r^2 ≡ A (mod P), None if P is not prime or A is not a quadratic residue
+pub const fn sqrt_mod_prime(a: u64, p: u64) -> Option<u64> {
+    if !is_prime(p) {
+        return None;
+    }
+    if p == 2 {
+        return Some(a & 1);
+    }
+    let inv = mul_inv2(p);
+    let one = one_mont(p);
+    let root = match sqrt_odd_prime(a % p, p, one, inv) {
+        Some(r) => mont_prod(r, 1, inv, p),
+        None => return None,
+    };
+    if root > p - root {
+        return Some(p - root);
+    }
+    Some(root)
+}
+
+// In: A odd, 2^K > A
+// Out: r with r^2 ≡ A (mod 2^K), None if A is not a square modulo 2^K
+const fn sqrt_mod_pow2(a: u64, k: u32) -> Option<u64> {
+    let mask = (1u64 << k) - 1;
+    if (k == 2 && a & 3 != 1) || (k > 2 && a & 7 != 1) {
+        return None;
+    }
+    // r^2 ≡ A (mod 2^(i+1)), if it fails modulo 2^(i+2) then (r + 2^i)^2 corrects it
+    let mut r = 1u64;
+    let mut i = 2;
+    while i + 1 < k {
+        if (r.wrapping_mul(r).wrapping_sub(a) >> (i + 1)) & 1 == 1 {
+            r += 1 << i;
+        }
+        i += 1;
+    }
+    Some(r & mask)
+}
+
+// In: A coprime to P, P odd prime, M = P^J
+// Out: r with r^2 ≡ A (mod M), None if A is not a quadratic residue modulo P
+const fn sqrt_mod_odd_power(a: u64, p: u64, m: u64) -> Option<u64> {
+    let inv = mul_inv2(p);
+    let one = one_mont(p);
+    let mut r = match sqrt_odd_prime(a % p, p, one, inv) {
+        Some(r) => r,
+        None => return None,
+    };
+    // Lift the least root, as sqrt_mod_prime returns it, negation commutes with the Montgomery form
+    let mut root = mont_prod(r, 1, inv, p);
+    if root > p - root {
+        root = p - root;
+        r = p - r;
+    }
+    if m == p {
+        return Some(root);
+    }
+    // r^-1 mod P as the initial inverse square root
+    let y = mont_prod(mont_pow(r, one, p - 2, inv, p), 1, inv, p);
+
+    let inv = mul_inv2(m);
+    let three = to_mont(3 % m, m);
+    let am = to_mont(a, m);
+    let mut y = to_mont(y, m);
+    // Each step doubles the number of correct base-p digits, 7 steps exceed 64 digits
+    let mut step = 0;
+    while step < 7 {
+        let ay2 = mont_prod(am, mont_prod(y, y, inv, m), inv, m);
+        y = half_mod(mont_prod(y, mont_sub(three, ay2, m), inv, m), m);
+        step += 1;
+    }
+    Some(mont_prod(mont_prod(am, y, inv, m), 1, inv, m))
+}
+
+/// Square root modulo a prime power
+///
+/// In: A, P, K
+///
+/// Out: Least r with r^2 ≡ A (mod P^K) among r and P^K - r, None if P is not prime, P^K overflows or A is not a
+///      quadratic residue
+pub const fn sqrt_mod_prime_power(a: u64, p: u64, k: u32) -> Option<u64> {
+    if !is_prime(p) {
+        return None;
+    }
+    let m = match p.checked_pow(k) {
+        Some(m) => m,
+        None => return None,
+    };
+    let a = a % m;
+    if a == 0 {
+        return Some(0);
+    }
+    // A = P^V B with P ∤ B, then √A = P^(V/2) √B (mod P^K) where √B is taken modulo P^(K-V)
+    let mut v = 0;
+    let mut b = a;
+    while b.is_multiple_of(p) {
+        b /= p;
+        v += 1;
+    }
+    if v & 1 == 1 {
+        return None;
+    }
+    let mk = p.pow(k - v);
+    let root = if p == 2 {
+        match sqrt_mod_pow2(b % mk, k - v) {
+            Some(r) => r,
+            None => return None,
+        }
+    } else {
+        match sqrt_mod_odd_power(b % mk, p, mk) {
+            Some(r) => r,
+            None => return None,
+        }
+    };
+    let root = root * p.pow(v / 2);
+    if root > m - root {
+        return Some(m - root);
+    }
+    Some(root)
+}
+
+// In: A,B < N
+// Out: AB mod N
+const fn mul_mod(a: u64, b: u64, n: u64) -> u64 {
+    ((a as u128 * b as u128) % n as u128) as u64
+}
+
+/// Chinese remainder theorem
+///
+/// In: Pairs (residue, modulus) with pairwise coprime moduli
+///
+/// Out: (x, M) where M is the product of the moduli and x < M satisfies every congruence, None if the moduli are not
+///      coprime, a modulus is zero or M overflows
+pub const fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let (mut x, mut m) = (0u64, 1u64);
+    let mut idx = 0;
+    while idx < congruences.len() {
+        let (b, n) = congruences[idx];
+        if n == 0 || gcd(m, n) != 1 {
+            return None;
+        }
+        let product = match m.checked_mul(n) {
+            Some(product) => product,
+            None => return None,
+        };
+        // x + m t ≡ b (mod n)
+        let minv = match mod_inverse(m as u128, n as u128) {
+            Some(minv) => minv as u64,
+            None => return None,
+        };
+        let diff = mont_sub(b % n, x % n, n);
+        let t = mul_mod(diff, minv, n);
+        x += m * t;
+        m = product;
+        idx += 1;
+    }
+    Some((x, m))
+}
+
+/// Square root modulo a composite
+///
+/// In: A, factorisation of N
+///
+/// Out: r with r^2 ≡ A (mod N), None if N = 0 or A is not a quadratic residue
+pub const fn sqrt_mod(a: u64, factors: &Factorization) -> Option<u64> {
+    let n = factors.n();
+    if n == 0 {
+        return None;
+    }
+    let (mut x, mut m) = (0u64, 1u64);
+    let mut idx = 0;
+    while let Some((p, e)) = factors.get(idx) {
+        let pk = p.pow(e);
+        let r = match sqrt_mod_prime_power(a % pk, p, e) {
+            Some(r) => r,
+            None => return None,
+        };
+        (x, m) = match crt(&[(x, m), (r, pk)]) {
+            Some(pair) => pair,
+            None => return None,
+        };
+        idx += 1;
+    }
+    Some(x)
+}
+
+// In: P odd prime, Mont(1,P), P^-1
+// Out: Mont(z,P) for the least quadratic non-residue z
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn non_residue_128(p: u128, one: u128, inv: u128) -> u128 {
+    let mut z = 2;
+    loop {
+        let zm = to_mont_128(z, p);
+        if mont_pow_128(zm, one, (p - 1) >> 1, inv, p) != one {
+            return zm;
+        }
+        z += 1;
+    }
+}
+
+// In: Mont(A,P) != 0, P odd prime with P-1 = 2^S Q
+// Out: Mont(√A,P), None if A is not a quadratic residue
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn tonelli_shanks_128(a: u128, p: u128, one: u128, inv: u128) -> Option<u128> {
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let mut c = mont_pow_128(non_residue_128(p, one, inv), one, q, inv, p);
+    let mut x = mont_pow_128(a, one, (q + 1) >> 1, inv, p);
+    let mut t = mont_pow_128(a, one, q, inv, p);
+    let mut m = s;
+    while t != one {
+        let mut i = 0;
+        let mut t2 = t;
+        while t2 != one {
+            t2 = mont_sqr_128(t2, inv, p);
+            i += 1;
+            if i == m {
+                return None;
+            }
+        }
+        let mut b = c;
+        let mut j = 0;
+        while j + i + 1 < m {
+            b = mont_sqr_128(b, inv, p);
+            j += 1;
+        }
+        x = mont_prod_128(x, b, inv, p);
+        c = mont_sqr_128(b, inv, p);
+        t = mont_prod_128(t, c, inv, p);
+        m = i;
+    }
+    Some(x)
+}
+
+// In: Mont(A,P) != 0, P odd prime
+// Out: Mont(√A,P), None if A is not a quadratic residue
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn cipolla_128(a: u128, p: u128, one: u128, inv: u128) -> Option<u128> {
+    if mont_pow_128(a, one, (p - 1) >> 1, inv, p) != one {
+        return None;
+    }
+    let mut t = one;
+    let mut w;
+    loop {
+        t = add_mod_128(t, one, p);
+        w = mont_sub_128(mont_sqr_128(t, inv, p), a, p);
+        if w != 0 && mont_pow_128(w, one, (p - 1) >> 1, inv, p) != one {
+            break;
+        }
+    }
+    let (mut x0, mut x1) = (one, 0);
+    let (mut b0, mut b1) = (t, one);
+    let mut e = (p >> 1) + 1;
+    while e > 0 {
+        if e & 1 == 1 {
+            let y0 = add_mod_128(mont_prod_128(x0, b0, inv, p), mont_prod_128(mont_prod_128(x1, b1, inv, p), w, inv, p), p);
+            x1 = add_mod_128(mont_prod_128(x0, b1, inv, p), mont_prod_128(x1, b0, inv, p), p);
+            x0 = y0;
+        }
+        let c0 = add_mod_128(mont_sqr_128(b0, inv, p), mont_prod_128(mont_sqr_128(b1, inv, p), w, inv, p), p);
+        let prod = mont_prod_128(b0, b1, inv, p);
+        b1 = add_mod_128(prod, prod, p);
+        b0 = c0;
+        e >>= 1;
+    }
+    Some(x0)
+}
+
+// In: A < P, P odd prime
+// Out: Mont(√A,P), None if A is not a quadratic residue
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn sqrt_odd_prime_128(a: u128, p: u128, one: u128, inv: u128) -> Option<u128> {
+    let am = to_mont_128(a, p);
+    if am == 0 {
+        return Some(0);
+    }
+    let s = (p - 1).trailing_zeros();
+    if s * s > 8 * (u128::BITS - p.leading_zeros()) {
+        return cipolla_128(am, p, one, inv);
+    }
+    tonelli_shanks_128(am, p, one, inv)
+}
+
+/// Square root modulo a prime, 128-bit form
+///
+/// In: A, P
+///
+/// Out: Least r such that r^2 ≡ A (mod P), None if P is not prime or A is not a quadratic residue
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn sqrt_mod_prime_128(a: u128, p: u128) -> Option<u128> {
+    if p < 2 {
+        return None;
+    }
+    if p <= u64::MAX as u128 {
+        return match sqrt_mod_prime((a % p) as u64, p as u64) {
+            Some(r) => Some(r as u128),
+            None => None,
+        };
+    }
+    if !is_prime_128(p) {
+        return None;
+    }
+    let inv = mul_inv2_128(p);
+    let one = one_mont_128(p);
+    let root = match sqrt_odd_prime_128(a % p, p, one, inv) {
+        Some(r) => mont_prod_128(r, 1, inv, p),
+        None => return None,
+    };
+    if root > p - root {
+        return Some(p - root);
+    }
+    Some(root)
+}
+
+// In: A odd, 2^K > A
+// Out: r with r^2 ≡ A (mod 2^K), None if A is not a square modulo 2^K
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn sqrt_mod_pow2_128(a: u128, k: u32) -> Option<u128> {
+    let mask = (1u128 << k) - 1;
+    if (k == 2 && a & 3 != 1) || (k > 2 && a & 7 != 1) {
+        return None;
+    }
+    let mut r = 1u128;
+    let mut i = 2;
+    while i + 1 < k {
+        if (r.wrapping_mul(r).wrapping_sub(a) >> (i + 1)) & 1 == 1 {
+            r += 1 << i;
+        }
+        i += 1;
+    }
+    Some(r & mask)
+}
+
+// In: A coprime to P, P odd prime, M = P^J
+// Out: r with r^2 ≡ A (mod M), None if A is not a quadratic residue modulo P
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn sqrt_mod_odd_power_128(a: u128, p: u128, m: u128) -> Option<u128> {
+    let r = if p <= u64::MAX as u128 {
+        match sqrt_mod_prime((a % p) as u64, p as u64) {
+            Some(r) => r as u128,
+            None => return None,
+        }
+    } else {
+        match sqrt_mod_prime_128(a % p, p) {
+            Some(r) => r,
+            None => return None,
+        }
+    };
+    if m == p {
+        return Some(r);
+    }
+    let y = match mod_inverse(r, p) {
+        Some(y) => y,
+        None => return None,
+    };
+
+    let inv = mul_inv2_128(m);
+    let three = to_mont_128(3 % m, m);
+    let am = to_mont_128(a, m);
+    let mut y = to_mont_128(y, m);
+    // Each step doubles the number of correct base-p digits, 8 steps exceed 128 digits
+    let mut step = 0;
+    while step < 8 {
+        let ay2 = mont_prod_128(am, mont_sqr_128(y, inv, m), inv, m);
+        y = half_mod_128(mont_prod_128(y, mont_sub_128(three, ay2, m), inv, m), m);
+        step += 1;
+    }
+    Some(mont_prod_128(mont_prod_128(am, y, inv, m), 1, inv, m))
+}
+
+/// Square root modulo a prime power, 128-bit form
+///
+/// In: A, P, K
+///
+/// Out: Least r with r^2 ≡ A (mod P^K) among r and P^K - r, None if P is not prime, P^K overflows or A is not a
+///      quadratic residue
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn sqrt_mod_prime_power_128(a: u128, p: u128, k: u32) -> Option<u128> {
+    if !is_prime_128(p) {
+        return None;
+    }
+    let m = match p.checked_pow(k) {
+        Some(m) => m,
+        None => return None,
+    };
+    let a = a % m;
+    if a == 0 {
+        return Some(0);
+    }
+    let mut v = 0;
+    let mut b = a;
+    while b.is_multiple_of(p) {
+        b /= p;
+        v += 1;
+    }
+    if v & 1 == 1 {
+        return None;
+    }
+    let mk = p.pow(k - v);
+    let root = if p == 2 {
+        match sqrt_mod_pow2_128(b % mk, k - v) {
+            Some(r) => r,
+            None => return None,
+        }
+    } else {
+        match sqrt_mod_odd_power_128(b % mk, p, mk) {
+            Some(r) => r,
+            None => return None,
+        }
+    };
+    let root = root * p.pow(v / 2);
+    if root > m - root {
+        return Some(m - root);
+    }
+    Some(root)
+}
+
+// In: A,B < N
+// Out: AB mod N
+#[cfg(any(feature = "wide", feature = "qft"))]
+const fn mul_mod_128(a: u128, b: u128, n: u128) -> u128 {
+    if n & 1 == 1 {
+        return mont_prod_128(to_mont_128(a, n), b, mul_inv2_128(n), n);
+    }
+    if n.is_power_of_two() {
+        return a.wrapping_mul(b) & (n - 1);
+    }
+    // Other even moduli by doubling and adding
+    let (mut x, mut y, mut acc) = (a, b, 0u128);
+    while y > 0 {
+        if y & 1 == 1 {
+            acc = add_mod_128(acc, x, n);
+        }
+        x = add_mod_128(x, x, n);
+        y >>= 1;
+    }
+    acc
+}
+
+/// Chinese remainder theorem, 128-bit form
+///
+/// In: Pairs (residue, modulus) with pairwise coprime moduli
+///
+/// Out: (x, M) where M is the product of the moduli and x < M satisfies every congruence, None if the moduli are not
+///      coprime, a modulus is zero or M overflows
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn crt_128(congruences: &[(u128, u128)]) -> Option<(u128, u128)> {
+    let (mut x, mut m) = (0u128, 1u128);
+    let mut idx = 0;
+    while idx < congruences.len() {
+        let (b, n) = congruences[idx];
+        if n == 0 || gcd_128(m, n) != 1 {
+            return None;
+        }
+        let product = match m.checked_mul(n) {
+            Some(product) => product,
+            None => return None,
+        };
+        let minv = match mod_inverse(m, n) {
+            Some(minv) => minv,
+            None => return None,
+        };
+        let diff = mont_sub_128(b % n, x % n, n);
+        let t = mul_mod_128(diff, minv, n);
+        x += m * t;
+        m = product;
+        idx += 1;
+    }
+    Some((x, m))
+}
+
+/// Square root modulo a composite, 128-bit form
+///
+/// In: A, factorisation of N
+///
+/// Out: r with r^2 ≡ A (mod N), None if N = 0 or A is not a quadratic residue
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn sqrt_mod_128(a: u128, factors: &Factorization128) -> Option<u128> {
+    let n = factors.n();
+    if n == 0 {
+        return None;
+    }
+    let (mut x, mut m) = (0u128, 1u128);
+    let mut idx = 0;
+    while let Some((p, e)) = factors.get(idx) {
+        let pk = p.pow(e);
+        let r = match sqrt_mod_prime_power_128(a % pk, p, e) {
+            Some(r) => r,
+            None => return None,
+        };
+        (x, m) = match crt_128(&[(x, m), (r, pk)]) {
+            Some(pair) => pair,
+            None => return None,
+        };
+        idx += 1;
+    }
+    Some(x)
+}
+
+#[cfg(all(test, any(feature = "wide", feature = "qft"), any(feature = "lucas", feature = "ssmr")))]
+mod tests {
+    use super::{sqrt_mod, sqrt_mod_128};
+    use crate::factor::factor;
+    use crate::factor_128::factor_128;
+
+    #[test]
+    fn widths_agree() {
+        assert_eq!(sqrt_mod(100, &factor(125)), Some(10));
+        for m in 2..600u64 {
+            let (f, f128) = (factor(m), factor_128(m as u128));
+            for a in 0..m {
+                assert_eq!(sqrt_mod(a, &f).map(|r| r as u128), sqrt_mod_128(a as u128, &f128));
+            }
+        }
+    }
+}
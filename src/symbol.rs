@@ -0,0 +1,181 @@
+use crate::check::is_prime;
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wide::is_prime_128;
+
+/*
+   Jacobi, Kronecker and Legendre symbols
+
+   The Jacobi symbol is evaluated by David Sparks' branchless variant of the binary algorithm. The sign is accumulated in
+   bit 1 of a word, each factor of 2 removed from a flips it if n ≡ 3,5 (mod 8) and quadratic reciprocity flips it if
+   a ≡ n ≡ 3 (mod 4). The loop ends with n = gcd(a,n), the symbol is 0 unless this is 1.
+
+   The 128-bit form takes 128-bit steps only until n fits in 64 bits, which is at most one step when a is small.
+
+   The Kronecker symbol extends the Jacobi symbol to all n by (a/-1) = sign(a) and (a/2) = 0 for even a, 1 for
+   a ≡ ±1 (mod 8) and -1 for a ≡ ±3 (mod 8).
+*/
+
+// In: A, N odd, sign accumulated so far
+// Out: gcd(A,N) and the accumulated sign
+const fn jacobi_loop(mut a: u64, mut n: u64, mut sign: u32) -> (u64, u32) {
+    while a != 0 {
+        // Remove factors of a that are powers of two
+        let twofactor = a.trailing_zeros();
+        a >>= twofactor;
+        // flip sign if n == 3 or 5 mod 8 and zeros is odd
+        sign ^= (n as u32).wrapping_add(2) >> 1 & twofactor << 1;
+        // if a == n == 3 mod 4 flip sign
+        sign ^= (n & a) as u32;
+
+        (a, n) = (n % a, a);
+    }
+    (n, sign)
+}
+
+// In: gcd and accumulated sign from the Jacobi loop
+// Out: Jacobi symbol
+const fn finish(gcd: u64, sign: u32) -> i8 {
+    if gcd != 1 {
+        return 0;
+    }
+    1 - (sign & 2) as i8
+}
+
+/// Jacobi symbol
+///
+/// In: A, N
+///
+/// Out: (A/N) ∈ {-1,0,1}, 0 if N is even
+pub const fn jacobi(a: u64, n: u64) -> i8 {
+    if n & 1 == 0 {
+        return 0;
+    }
+    let (gcd, sign) = jacobi_loop(a, n, 0);
+    finish(gcd, sign)
+}
+
+/// Legendre symbol
+///
+/// In: A, P
+///
+/// Out: (A/P) ∈ {-1,0,1}, None if P is not an odd prime
+pub const fn legendre(a: u64, p: u64) -> Option<i8> {
+    if p == 2 || !is_prime(p) {
+        return None;
+    }
+    Some(jacobi(a, p))
+}
+
+// In: |A|, A < 0, N > 0
+// Out: A mod N
+const fn signed_rem(a: u128, negative: bool, n: u128) -> u128 {
+    let rem = a % n;
+    if negative && rem != 0 {
+        return n - rem;
+    }
+    rem
+}
+
+/// Kronecker symbol
+///
+/// In: A, N
+///
+/// Out: (A/N) ∈ {-1,0,1}
+pub const fn kronecker(a: i64, n: i64) -> i8 {
+    let magnitude = a.unsigned_abs();
+    if n == 0 {
+        return (magnitude == 1) as i8;
+    }
+    let mut sign = 1;
+    if n < 0 && a < 0 {
+        sign = -1;
+    }
+    let mut m = n.unsigned_abs();
+    let zeros = m.trailing_zeros();
+    if zeros != 0 {
+        if a & 1 == 0 {
+            return 0;
+        }
+        m >>= zeros;
+        let r = magnitude & 7;
+        if zeros & 1 == 1 && (r == 3 || r == 5) {
+            sign = -sign;
+        }
+    }
+    let reduced = signed_rem(magnitude as u128, a < 0, m as u128) as u64;
+    sign * jacobi(reduced, m)
+}
+
+/// Jacobi symbol, 128-bit form
+///
+/// In: A, N
+///
+/// Out: (A/N) ∈ {-1,0,1}, 0 if N is even
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn jacobi_128(a: u128, n: u128) -> i8 {
+    if n & 1 == 0 {
+        return 0;
+    }
+    if n >> 64 == 0 {
+        return jacobi((a % n) as u64, n as u64);
+    }
+    let (mut a, mut n) = (a % n, n);
+    let mut sign: u32 = 0;
+    // 128-bit steps until both values fit into 64 bits
+    while n >> 64 != 0 {
+        if a == 0 {
+            return 0;
+        }
+        let zeros = a.trailing_zeros();
+        a >>= zeros;
+        sign ^= (n as u32).wrapping_add(2) >> 1 & zeros << 1;
+        sign ^= a as u32 & n as u32;
+        (a, n) = (n % a, a);
+    }
+    let (gcd, sign) = jacobi_loop(a as u64, n as u64, sign);
+    finish(gcd, sign)
+}
+
+/// Legendre symbol, 128-bit form
+///
+/// In: A, P
+///
+/// Out: (A/P) ∈ {-1,0,1}, None if P is not an odd prime
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn legendre_128(a: u128, p: u128) -> Option<i8> {
+    if p == 2 || !is_prime_128(p) {
+        return None;
+    }
+    Some(jacobi_128(a, p))
+}
+
+/// Kronecker symbol, 128-bit form
+///
+/// In: A, N
+///
+/// Out: (A/N) ∈ {-1,0,1}
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn kronecker_128(a: i128, n: i128) -> i8 {
+    let magnitude = a.unsigned_abs();
+    if n == 0 {
+        return (magnitude == 1) as i8;
+    }
+    let mut sign = 1;
+    if n < 0 && a < 0 {
+        sign = -1;
+    }
+    let mut m = n.unsigned_abs();
+    let zeros = m.trailing_zeros();
+    if zeros != 0 {
+        if a & 1 == 0 {
+            return 0;
+        }
+        m >>= zeros;
+        let r = magnitude & 7;
+        if zeros & 1 == 1 && (r == 3 || r == 5) {
+            sign = -sign;
+        }
+    }
+    sign * jacobi_128(signed_rem(magnitude, a < 0, m), m)
+}
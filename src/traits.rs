@@ -0,0 +1,120 @@
+use core::num::{
+    NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU16, NonZeroU32, NonZeroU64,
+    NonZeroU8, NonZeroUsize,
+};
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use core::num::{NonZeroI128, NonZeroU128};
+
+use crate::check::{is_prime, is_prime_wc};
+use crate::narrow::{is_prime_32, is_prime_wc_32};
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wide::{is_prime_128, is_prime_wc_128};
+
+/// Primality testing for the primitive integers
+///
+/// Each width is evaluated by the cheapest backend that covers it, u8, u16 and u32 use the 32-bit functions,
+/// u64 the 64-bit functions and u128 the 128-bit functions (requires Wide or QFT). usize is evaluated according to
+/// the pointer width of the target.
+///
+/// Signed integers are evaluated by their absolute value, so -7 is prime and i64::MIN is not. This is the
+/// convention of primes as elements of Z, where n and -n are associates.
+///
+/// is_prime_wc has the same failure points as the function it dispatches to, see is_prime_wc.
+pub trait Primality: Copy {
+    /// Primality testing optimized for the average case
+    fn is_prime(self) -> bool;
+
+    /// Primality testing for the worst case
+    fn is_prime_wc(self) -> bool;
+}
+
+macro_rules! impl_primality {
+    ($($t:ty => $prime:ident, $prime_wc:ident, $cast:ty;)*) => {
+        $(
+            impl Primality for $t {
+                #[inline]
+                fn is_prime(self) -> bool {
+                    $prime(self as $cast)
+                }
+
+                #[inline]
+                fn is_prime_wc(self) -> bool {
+                    $prime_wc(self as $cast)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_signed_primality {
+    ($($t:ty),*) => {
+        $(
+            impl Primality for $t {
+                #[inline]
+                fn is_prime(self) -> bool {
+                    self.unsigned_abs().is_prime()
+                }
+
+                #[inline]
+                fn is_prime_wc(self) -> bool {
+                    self.unsigned_abs().is_prime_wc()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_nonzero_primality {
+    ($($t:ty),*) => {
+        $(
+            impl Primality for $t {
+                #[inline]
+                fn is_prime(self) -> bool {
+                    self.get().is_prime()
+                }
+
+                #[inline]
+                fn is_prime_wc(self) -> bool {
+                    self.get().is_prime_wc()
+                }
+            }
+        )*
+    };
+}
+
+impl_primality! {
+    u8 => is_prime_32, is_prime_wc_32, u32;
+    u16 => is_prime_32, is_prime_wc_32, u32;
+    u32 => is_prime_32, is_prime_wc_32, u32;
+    u64 => is_prime, is_prime_wc, u64;
+}
+
+#[cfg(any(target_pointer_width = "16", target_pointer_width = "32"))]
+impl_primality! {
+    usize => is_prime_32, is_prime_wc_32, u32;
+}
+
+#[cfg(target_pointer_width = "64")]
+impl_primality! {
+    usize => is_prime, is_prime_wc, u64;
+}
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl_primality! {
+    u128 => is_prime_128, is_prime_wc_128, u128;
+}
+
+impl_signed_primality!(i8, i16, i32, i64, isize);
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl_signed_primality!(i128);
+
+impl_nonzero_primality!(
+    NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroUsize, NonZeroI8, NonZeroI16, NonZeroI32,
+    NonZeroI64, NonZeroIsize
+);
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+impl_nonzero_primality!(NonZeroU128, NonZeroI128);
@@ -0,0 +1,221 @@
+use crate::check::is_prime;
+
+#[cfg(any(feature = "wide", feature = "qft"))]
+use crate::wide::is_prime_128;
+
+/*
+   Prime stepping
+
+   Candidates are restricted to the 48 residues coprime to 2*3*5*7 = 210, eliminating 77% of integers before
+   is_prime is called. is_prime then performs trial division by the remaining small primes prior to the
+   strong fermat tests.
+*/
+
+/// Modulus of the wheel
+pub(crate) const WHEEL_MOD: u64 = 210;
+
+/// Residues coprime to 210
+pub(crate) const WHEEL: [u8; 48] = wheel_residues();
+
+const fn wheel_residues() -> [u8; 48] {
+    let mut residues = [0u8; 48];
+    let mut idx = 0;
+    let mut r = 1u8;
+    while r < 210 {
+        if !r.is_multiple_of(2) && !r.is_multiple_of(3) && !r.is_multiple_of(5) && !r.is_multiple_of(7) {
+            residues[idx] = r;
+            idx += 1;
+        }
+        r += 1;
+    }
+    residues
+}
+
+// In: R < 210
+// Out: Index of the least wheel residue >= R, 48 if there is none
+pub(crate) const fn wheel_index_up(r: u8) -> usize {
+    let mut idx = 0;
+    while idx < 48 && WHEEL[idx] < r {
+        idx += 1;
+    }
+    idx
+}
+
+// In: R < 210
+// Out: Index of the greatest wheel residue <= R, None if R == 0
+pub(crate) const fn wheel_index_down(r: u8) -> Option<usize> {
+    let mut idx = 48;
+    while idx > 0 {
+        idx -= 1;
+        if WHEEL[idx] <= r {
+            return Some(idx);
+        }
+    }
+    None
+}
+
+/// Primes less than 11, the first prime that the wheel iterates over
+const SMALL: [u64; 4] = [2, 3, 5, 7];
+
+/// Next prime
+///
+/// In: N
+///
+/// Out: Least prime P > N, None if P > 2^64
+pub const fn next_prime(n: u64) -> Option<u64> {
+    if n < 7 {
+        let mut idx = 0;
+        while SMALL[idx] <= n {
+            idx += 1;
+        }
+        return Some(SMALL[idx]);
+    }
+    // Largest 64-bit prime
+    if n >= 0xFFFFFFFFFFFFFFC5 {
+        return None;
+    }
+
+    let start = n + 1;
+    let mut base = start - start % WHEEL_MOD;
+    let mut idx = wheel_index_up((start % WHEEL_MOD) as u8);
+
+    if idx == 48 {
+        idx = 0;
+        base += WHEEL_MOD;
+    }
+    // A prime exists below 2^64 so the candidate can never overflow
+    loop {
+        let candidate = base + WHEEL[idx] as u64;
+        if is_prime(candidate) {
+            return Some(candidate);
+        }
+        idx += 1;
+        if idx == 48 {
+            idx = 0;
+            base += WHEEL_MOD;
+        }
+    }
+}
+
+/// Previous prime
+///
+/// In: N
+///
+/// Out: Greatest prime P < N, None if N <= 2
+pub const fn prev_prime(n: u64) -> Option<u64> {
+    if n <= 2 {
+        return None;
+    }
+    if n <= 11 {
+        let mut idx = 3;
+        while SMALL[idx] >= n {
+            idx -= 1;
+        }
+        return Some(SMALL[idx]);
+    }
+
+    let start = n - 1;
+    let mut base = start - start % WHEEL_MOD;
+    let mut idx = match wheel_index_down((start % WHEEL_MOD) as u8) {
+        Some(idx) => idx,
+        None => {
+            base -= WHEEL_MOD;
+            47
+        }
+    };
+    // 11 is a wheel residue so the search terminates before base underflows
+    loop {
+        let candidate = base + WHEEL[idx] as u64;
+        if is_prime(candidate) {
+            return Some(candidate);
+        }
+        if idx == 0 {
+            idx = 47;
+            base -= WHEEL_MOD;
+        } else {
+            idx -= 1;
+        }
+    }
+}
+
+/// Next prime, 128-bit form
+///
+/// In: N
+///
+/// Out: Least prime P > N, None if P > 2^128
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn next_prime_128(n: u128) -> Option<u128> {
+    if n < 0xFFFFFFFFFFFFFFC5 {
+        return match next_prime(n as u64) {
+            Some(p) => Some(p as u128),
+            None => None,
+        };
+    }
+    // Largest 128-bit prime
+    if n >= 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF61 {
+        return None;
+    }
+
+    const MODULUS: u128 = WHEEL_MOD as u128;
+
+    let start = n + 1;
+    let mut base = start - start % MODULUS;
+    let mut idx = wheel_index_up((start % MODULUS) as u8);
+
+    if idx == 48 {
+        idx = 0;
+        base += MODULUS;
+    }
+
+    loop {
+        let candidate = base + WHEEL[idx] as u128;
+        if is_prime_128(candidate) {
+            return Some(candidate);
+        }
+        idx += 1;
+        if idx == 48 {
+            idx = 0;
+            base += MODULUS;
+        }
+    }
+}
+
+/// Previous prime, 128-bit form
+///
+/// In: N
+///
+/// Out: Greatest prime P < N, None if N <= 2
+#[cfg(any(feature = "wide", feature = "qft"))]
+pub const fn prev_prime_128(n: u128) -> Option<u128> {
+    if n < 0x10000000000000000 {
+        return match prev_prime(n as u64) {
+            Some(p) => Some(p as u128),
+            None => None,
+        };
+    }
+
+    const MODULUS: u128 = WHEEL_MOD as u128;
+
+    let start = n - 1;
+    let mut base = start - start % MODULUS;
+    let mut idx = match wheel_index_down((start % MODULUS) as u8) {
+        Some(idx) => idx,
+        None => {
+            base -= MODULUS;
+            47
+        }
+    };
+
+    loop {
+        let candidate = base + WHEEL[idx] as u128;
+        if is_prime_128(candidate) {
+            return Some(candidate);
+        }
+        if idx == 0 {
+            idx = 47;
+            base -= MODULUS;
+        } else {
+            idx -= 1;
+        }
+    }
+}
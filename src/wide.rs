@@ -5,6 +5,7 @@ use crate::primes::PRIME_TABLE_128;
 use crate::qft::qft;
 
 use crate::check::mul_inv2;
+use crate::symbol::jacobi_128;
 
 /// Multiplicative inverse over Z/2^128
 ///
@@ -29,38 +30,8 @@ const fn split_to_u128(x: u128) -> (u128, u128) {
 ///
 /// Out: Jacobi(A,K) == -1
 pub const fn nqr_128(a: u128, n: u128) -> bool {
-     // The final sign is bit 1 of this variable.
-     // The other 31 bits are garbage that are masked at the end.
-    let mut sign: u32 = 0;
-
-    let (mut a64, mut n64) = if n >> 64 == 0 {
-        (a as u64, n as u64)
-    } else {
-        // We need one iteration with 128-bit types.
-        if n == 0 {
-            return false;
-        }
-        // Remove powers of 2 from a
-        let zeros = a.trailing_zeros();
-        let a = a >> zeros;
-        // Flip sign if p == 3 or 5 (mod 8) and zeros is odd
-        sign ^= (n as u32).wrapping_add(2) >> 1 & zeros << 1;
-
-        // Quadratic recoprocity: Flip sign if p == n == 3 (mod 8)
-        sign ^= a as u32 & n as u32;
-        // This ensures both values fit into 64 bits
-        ((n % a) as u64, a as u64)
-    };
-    
-     while a64 != 0 {
-         let zeros = a64.trailing_zeros();
-         a64 >>= zeros;
-         sign ^= (n64 as u32).wrapping_add(2) >> 1 & zeros << 1;
-         sign ^= (a64 & n64) as u32;
-        (a64, n64) = (n64 % a64, a64)
-     }
-     n64 == 1 && sign & 2 != 0
-  }   
+    jacobi_128(a, n) == -1
+}
 
 /// Lucas parameter search, 128-bit form
 ///
@@ -187,6 +158,35 @@ pub const fn mont_sub_128(x: u128, y: u128, n: u128) -> u128 {
     }
 }
 
+// In: X,Y < N
+// Out: X+Y mod N
+pub(crate) const fn add_mod_128(x: u128, y: u128, n: u128) -> u128 {
+    let (sum, carry) = x.overflowing_add(y);
+    if carry || sum >= n {
+        return sum.wrapping_sub(n);
+    }
+    sum
+}
+
+// In: X < N, N odd
+// Out: X/2 mod N
+pub(crate) const fn half_mod_128(x: u128, n: u128) -> u128 {
+    if x & 1 == 0 {
+        return x >> 1;
+    }
+    (x >> 1) + (n >> 1) + 1
+}
+
+// In: X, N > 0
+// Out: X mod N
+pub(crate) const fn signed_mod_128(x: i128, n: u128) -> u128 {
+    let rem = x.unsigned_abs() % n;
+    if x < 0 && rem != 0 {
+        return n - rem;
+    }
+    rem
+}
+
 /// Convert to Montgomery form, 128-bit form
 ///
 /// In: X, N where X < N